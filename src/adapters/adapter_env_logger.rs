@@ -1,5 +1,7 @@
-use crate::types::LogLevel;
-use crate::{logger::Logger as VigilantLogger, EnvLoggerAdapterBuilder};
+use crate::logger::Logger as VigilantLogger;
+use crate::types::{Attribute, LogLevel};
+#[allow(deprecated)]
+use crate::EnvLoggerAdapterBuilder;
 use env_logger::{Builder as EnvLoggerBuilder, Logger as EnvLogger};
 use log::{Level, Log, Metadata, Record};
 use std::sync::Arc;
@@ -25,7 +27,13 @@ impl EnvLoggerAdapter {
         }
     }
 
-    pub fn builder<'a>() -> EnvLoggerAdapterBuilder<'a> {
+    pub fn from_logger(vigilant_logger: VigilantLogger) -> Self {
+        Self::new(vigilant_logger)
+    }
+
+    #[deprecated(note = "use `LoggerBuilder::log_adapter` instead")]
+    #[allow(deprecated)]
+    pub fn builder() -> EnvLoggerAdapterBuilder {
         EnvLoggerAdapterBuilder::new()
     }
 
@@ -49,12 +57,25 @@ impl Log for EnvLoggerAdapter {
             Level::Debug | Level::Trace => LogLevel::DEBUG,
         };
 
+        let mut attributes = vec![Attribute::new("target", record.target())];
+        if let Some(file) = record.file() {
+            attributes.push(Attribute::new("file", file));
+        }
+        if let Some(line) = record.line() {
+            attributes.push(Attribute::new("line", line.to_string()));
+        }
+        if let Some(module_path) = record.module_path() {
+            attributes.push(Attribute::new("module_path", module_path));
+        }
+
+        let logger = &self.inner.vigilant_logger;
         let message = record.args().to_string();
         match level {
-            LogLevel::ERROR => self.inner.vigilant_logger.error(&message),
-            LogLevel::WARNING => self.inner.vigilant_logger.warn(&message),
-            LogLevel::INFO => self.inner.vigilant_logger.info(&message),
-            LogLevel::DEBUG => self.inner.vigilant_logger.debug(&message),
+            LogLevel::ERROR => logger.error_with_attrs(&message, attributes),
+            LogLevel::WARNING => logger.warn_with_attrs(&message, attributes),
+            LogLevel::INFO => logger.info_with_attrs(&message, attributes),
+            LogLevel::DEBUG => logger.debug_with_attrs(&message, attributes),
+            LogLevel::FATAL => logger.fatal_with_attrs(&message, attributes),
         }
     }
 
@@ -1,66 +1,87 @@
+use crate::config::VigilantConfig;
+use crate::error::VigilantError;
+use crate::types::LogLevel;
 use crate::{EnvLoggerAdapter, LoggerBuilder};
 
-pub struct EnvLoggerAdapterBuilder<'a> {
-    name: &'a str,
-    endpoint: &'a str,
-    token: &'a str,
-    passthrough: bool,
-    insecure: bool,
-    noop: bool,
+#[deprecated(note = "use `LoggerBuilder::log_adapter` instead")]
+pub struct EnvLoggerAdapterBuilder {
+    logger_builder: LoggerBuilder,
 }
 
-impl<'a> EnvLoggerAdapterBuilder<'a> {
+#[allow(deprecated)]
+impl Default for EnvLoggerAdapterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(deprecated)]
+impl EnvLoggerAdapterBuilder {
+    pub fn from_env() -> Self {
+        Self {
+            logger_builder: LoggerBuilder::from_env(),
+        }
+    }
+
+    pub fn from_config(config: VigilantConfig) -> Self {
+        Self {
+            logger_builder: LoggerBuilder::from_config(config),
+        }
+    }
+
     pub fn new() -> Self {
         Self {
-            name: "sample-app",
-            endpoint: "ingress.vigilant.run",
-            token: "tk_1234567890",
-            passthrough: false,
-            insecure: false,
-            noop: false,
+            logger_builder: LoggerBuilder::new(),
         }
     }
 
-    pub fn name(mut self, name: &'a str) -> Self {
-        self.name = name;
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.logger_builder = self.logger_builder.name(name);
+        self
+    }
+
+    pub fn endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.logger_builder = self.logger_builder.endpoint(endpoint);
         self
     }
 
-    pub fn endpoint(mut self, endpoint: &'a str) -> Self {
-        self.endpoint = endpoint;
+    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+        self.logger_builder = self.logger_builder.token(token);
         self
     }
 
-    pub fn token(mut self, token: &'a str) -> Self {
-        self.token = token;
+    pub fn dsn(mut self, dsn: &str) -> Self {
+        self.logger_builder = self.logger_builder.dsn(dsn);
         self
     }
 
     pub fn passthrough(mut self, enabled: bool) -> Self {
-        self.passthrough = enabled;
+        self.logger_builder = self.logger_builder.passthrough(enabled);
         self
     }
 
     pub fn insecure(mut self, enabled: bool) -> Self {
-        self.insecure = enabled;
+        self.logger_builder = self.logger_builder.insecure(enabled);
         self
     }
 
     pub fn noop(mut self, enabled: bool) -> Self {
-        self.noop = enabled;
+        self.logger_builder = self.logger_builder.noop(enabled);
+        self
+    }
+
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.logger_builder = self.logger_builder.level(level);
         self
     }
 
     pub fn build(self) -> EnvLoggerAdapter {
-        let vigilant_logger = LoggerBuilder::new()
-            .name(self.name)
-            .endpoint(self.endpoint)
-            .token(self.token)
-            .passthrough(self.passthrough)
-            .insecure(self.insecure)
-            .noop(self.noop)
-            .build();
+        self.logger_builder.log_adapter()
+    }
 
-        EnvLoggerAdapter::new(vigilant_logger)
+    pub fn try_build(self) -> Result<EnvLoggerAdapter, VigilantError> {
+        Ok(EnvLoggerAdapter::from_logger(
+            self.logger_builder.try_build()?,
+        ))
     }
 }
@@ -0,0 +1,121 @@
+use crate::context;
+use crate::logger::Logger as VigilantLogger;
+use crate::redaction::MASK;
+use crate::types::Attribute;
+use crate::util::random_u64;
+use reqwest::header::HeaderValue;
+use reqwest::{Request, Response, Url};
+use reqwest_middleware::{Middleware, Next, Result};
+use std::time::Instant;
+use task_local_extensions::Extensions;
+
+const TRACEPARENT_HEADER: &str = "traceparent";
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+#[derive(Clone, Copy)]
+struct Attempt(u32);
+
+#[derive(Clone)]
+pub struct ReqwestLogMiddleware {
+    logger: VigilantLogger,
+}
+
+impl ReqwestLogMiddleware {
+    pub fn from_logger(logger: VigilantLogger) -> Self {
+        Self { logger }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for ReqwestLogMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let attempt = extensions.get::<Attempt>().map_or(1, |a| a.0 + 1);
+        extensions.insert(Attempt(attempt));
+
+        let request_id = context::current()
+            .into_iter()
+            .rev()
+            .find(|attr| attr.key == "request.id")
+            .map(|attr| attr.value);
+        let trace_id = request_id
+            .as_deref()
+            .filter(|id| id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_else(|| format!("{:016x}{:016x}", random_u64(), random_u64()));
+        let span_id = format!("{:016x}", random_u64());
+
+        let headers = req.headers_mut();
+        if let Ok(value) = HeaderValue::from_str(&format!("00-{}-{}-01", trace_id, span_id)) {
+            headers.insert(TRACEPARENT_HEADER, value);
+        }
+        if let Some(value) = request_id
+            .as_deref()
+            .and_then(|id| HeaderValue::from_str(id).ok())
+        {
+            headers.entry(REQUEST_ID_HEADER).or_insert(value);
+        }
+
+        let method = req.method().to_string();
+        let raw_url = req.url().to_string();
+        let url = redact_url(req.url());
+        let mut attributes = vec![
+            Attribute::new("http.method", method.clone()),
+            Attribute::new("http.url", url.clone()),
+            Attribute::new("http.attempt", attempt.to_string()),
+            Attribute::new("trace.id", trace_id),
+            Attribute::new("span.id", span_id),
+        ];
+
+        let start = Instant::now();
+        let result = next.run(req, extensions).await;
+        attributes.push(Attribute::new(
+            "http.duration_ms",
+            format!("{:.3}", start.elapsed().as_secs_f64() * 1000.0),
+        ));
+
+        match &result {
+            Ok(response) => {
+                let status = response.status();
+                attributes.push(Attribute::new("http.status_code", status.as_str()));
+                let message = format!("{} {} {}", method, url, status.as_u16());
+                if status.is_server_error() {
+                    self.logger.error_with_attrs(&message, attributes);
+                } else {
+                    self.logger.info_with_attrs(&message, attributes);
+                }
+            }
+            Err(e) => {
+                attributes.push(Attribute::new(
+                    "error",
+                    e.to_string().replace(&raw_url, &url),
+                ));
+                self.logger
+                    .error_with_attrs(&format!("{} {} failed", method, url), attributes);
+            }
+        }
+        result
+    }
+}
+
+fn redact_url(url: &Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    if let Some(query) = url.query() {
+        let redacted: Vec<String> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let key = pair.split('=').next().unwrap_or_default();
+                format!("{}={}", key, MASK)
+            })
+            .collect();
+        url.set_query(Some(&redacted.join("&")));
+    }
+    url.to_string()
+}
@@ -0,0 +1,88 @@
+use crate::logger::Logger as VigilantLogger;
+use crate::types::{Attribute, LogLevel};
+#[allow(deprecated)]
+use crate::SlogDrainBuilder;
+use slog::{Drain, Key, Level, OwnedKVList, Record, Serializer, KV};
+use std::fmt::Arguments;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct SlogDrain {
+    inner: Arc<SlogDrainInner>,
+}
+
+struct SlogDrainInner {
+    vigilant_logger: VigilantLogger,
+}
+
+impl SlogDrain {
+    pub fn new(vigilant_logger: VigilantLogger) -> Self {
+        Self {
+            inner: Arc::new(SlogDrainInner { vigilant_logger }),
+        }
+    }
+
+    pub fn from_logger(vigilant_logger: VigilantLogger) -> Self {
+        Self::new(vigilant_logger)
+    }
+
+    #[deprecated(note = "use `LoggerBuilder::slog_drain` instead")]
+    #[allow(deprecated)]
+    pub fn builder() -> SlogDrainBuilder {
+        SlogDrainBuilder::new()
+    }
+
+    pub fn shutdown(&self) -> std::io::Result<()> {
+        self.inner.vigilant_logger.shutdown()
+    }
+}
+
+impl Drain for SlogDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let logger = &self.inner.vigilant_logger;
+
+        let level = match record.level() {
+            Level::Critical => LogLevel::FATAL,
+            Level::Error => LogLevel::ERROR,
+            Level::Warning => LogLevel::WARNING,
+            Level::Info => LogLevel::INFO,
+            Level::Debug | Level::Trace => LogLevel::DEBUG,
+        };
+
+        let mut serializer = AttributeSerializer(Vec::new());
+        let _ = values.serialize(record, &mut serializer);
+        let _ = record.kv().serialize(record, &mut serializer);
+        let mut attributes = serializer.0;
+
+        if !record.tag().is_empty() {
+            attributes.push(Attribute::new("tag", record.tag()));
+        }
+        attributes.push(Attribute::new("file", record.file()));
+        attributes.push(Attribute::new("line", record.line().to_string()));
+        attributes.push(Attribute::new("module_path", record.module()));
+
+        let message = record.msg().to_string();
+        match level {
+            LogLevel::ERROR => logger.error_with_attrs(&message, attributes),
+            LogLevel::WARNING => logger.warn_with_attrs(&message, attributes),
+            LogLevel::INFO => logger.info_with_attrs(&message, attributes),
+            LogLevel::DEBUG => logger.debug_with_attrs(&message, attributes),
+            LogLevel::FATAL => logger.fatal_with_attrs(&message, attributes),
+        }
+
+        Ok(())
+    }
+}
+
+struct AttributeSerializer(Vec<Attribute>);
+
+impl Serializer for AttributeSerializer {
+    fn emit_arguments(&mut self, key: Key, val: &Arguments) -> slog::Result {
+        self.0
+            .push(Attribute::new(key.to_string(), val.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::config::VigilantConfig;
+use crate::error::VigilantError;
+use crate::types::LogLevel;
+use crate::{LoggerBuilder, SlogDrain};
+
+#[deprecated(note = "use `LoggerBuilder::slog_drain` instead")]
+pub struct SlogDrainBuilder {
+    logger_builder: LoggerBuilder,
+}
+
+#[allow(deprecated)]
+impl Default for SlogDrainBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(deprecated)]
+impl SlogDrainBuilder {
+    pub fn from_env() -> Self {
+        Self {
+            logger_builder: LoggerBuilder::from_env(),
+        }
+    }
+
+    pub fn from_config(config: VigilantConfig) -> Self {
+        Self {
+            logger_builder: LoggerBuilder::from_config(config),
+        }
+    }
+
+    pub fn new() -> Self {
+        Self {
+            logger_builder: LoggerBuilder::new(),
+        }
+    }
+
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.logger_builder = self.logger_builder.name(name);
+        self
+    }
+
+    pub fn endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.logger_builder = self.logger_builder.endpoint(endpoint);
+        self
+    }
+
+    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+        self.logger_builder = self.logger_builder.token(token);
+        self
+    }
+
+    pub fn dsn(mut self, dsn: &str) -> Self {
+        self.logger_builder = self.logger_builder.dsn(dsn);
+        self
+    }
+
+    pub fn passthrough(mut self, enabled: bool) -> Self {
+        self.logger_builder = self.logger_builder.passthrough(enabled);
+        self
+    }
+
+    pub fn insecure(mut self, enabled: bool) -> Self {
+        self.logger_builder = self.logger_builder.insecure(enabled);
+        self
+    }
+
+    pub fn noop(mut self, enabled: bool) -> Self {
+        self.logger_builder = self.logger_builder.noop(enabled);
+        self
+    }
+
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.logger_builder = self.logger_builder.level(level);
+        self
+    }
+
+    pub fn build(self) -> SlogDrain {
+        self.logger_builder.slog_drain()
+    }
+
+    pub fn try_build(self) -> Result<SlogDrain, VigilantError> {
+        Ok(SlogDrain::from_logger(self.logger_builder.try_build()?))
+    }
+}
@@ -0,0 +1,287 @@
+use crate::context;
+use crate::logger::Logger as VigilantLogger;
+use crate::types::Attribute;
+use crate::util::random_u64;
+use http::header::{HeaderName, HeaderValue, CONTENT_LENGTH, USER_AGENT};
+use http::request::Parts;
+use http::{Request, Response};
+use http_body::Body;
+use pin_project_lite::pin_project;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower_layer::Layer;
+use tower_service::Service;
+
+const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+const TRACEPARENT_HEADER: HeaderName = HeaderName::from_static("traceparent");
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+type RouteExtractor = Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct HttpLogLayer {
+    logger: VigilantLogger,
+    route_extractor: Option<RouteExtractor>,
+}
+
+impl HttpLogLayer {
+    pub fn from_logger(logger: VigilantLogger) -> Self {
+        Self {
+            logger,
+            route_extractor: None,
+        }
+    }
+
+    pub fn route_extractor<F>(mut self, extractor: F) -> Self
+    where
+        F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+    {
+        self.route_extractor = Some(Arc::new(extractor));
+        self
+    }
+}
+
+impl<S> Layer<S> for HttpLogLayer {
+    type Service = HttpLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HttpLogService {
+            inner,
+            logger: self.logger.clone(),
+            route_extractor: self.route_extractor.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpLogService<S> {
+    inner: S,
+    logger: VigilantLogger,
+    route_extractor: Option<RouteExtractor>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for HttpLogService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Error: Display,
+    ResBody: Body,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = HttpLogFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let (parts, body) = request.into_parts();
+        let route = self
+            .route_extractor
+            .as_ref()
+            .and_then(|extractor| extractor(&parts));
+        let mut request = Request::from_parts(parts, body);
+
+        let request_id = request
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| is_valid_request_id(value))
+            .map(str::to_string)
+            .or_else(|| {
+                request
+                    .headers()
+                    .get(TRACEPARENT_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(traceparent_trace_id)
+            })
+            .unwrap_or_else(|| format!("{:016x}{:016x}", random_u64(), random_u64()));
+        if let Ok(value) = HeaderValue::from_str(&request_id) {
+            request.headers_mut().insert(REQUEST_ID_HEADER, value);
+        }
+
+        let mut attributes = vec![
+            Attribute::new("request.id", request_id.clone()),
+            Attribute::new("http.method", request.method().as_str()),
+            Attribute::new("url.path", request.uri().path()),
+        ];
+        if let Some(route) = route {
+            attributes.push(Attribute::new("http.route", route));
+        }
+        if let Some(user_agent) = request
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+        {
+            attributes.push(Attribute::new("http.user_agent", user_agent));
+        }
+
+        let context = vec![Attribute::new("request.id", request_id.clone())];
+        let inner = {
+            let _guard = context::enter(context.clone());
+            self.inner.call(request)
+        };
+
+        HttpLogFuture {
+            inner,
+            logger: self.logger.clone(),
+            context,
+            attributes,
+            request_id,
+            start: Instant::now(),
+        }
+    }
+}
+
+pin_project! {
+    pub struct HttpLogFuture<F> {
+        #[pin]
+        inner: F,
+        logger: VigilantLogger,
+        context: Vec<Attribute>,
+        attributes: Vec<Attribute>,
+        request_id: String,
+        start: Instant,
+    }
+}
+
+impl<F, ResBody, E> Future for HttpLogFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+    E: Display,
+    ResBody: Body,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = {
+            let _guard = context::enter(this.context.clone());
+            match this.inner.poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            }
+        };
+
+        let mut attributes = std::mem::take(this.attributes);
+        let method = attribute_value(&attributes, "http.method");
+        let route = attributes
+            .iter()
+            .find(|attr| attr.key == "http.route")
+            .map(|attr| attr.value.clone())
+            .unwrap_or_else(|| attribute_value(&attributes, "url.path"));
+        attributes.push(Attribute::new(
+            "http.duration_ms",
+            format!("{:.3}", this.start.elapsed().as_secs_f64() * 1000.0),
+        ));
+
+        match result {
+            Ok(mut response) => {
+                let status = response.status();
+                attributes.push(Attribute::new("http.status_code", status.as_str()));
+                if let Some(size) = response_size(&response) {
+                    attributes.push(Attribute::new("http.response_size", size.to_string()));
+                }
+                if let Ok(value) = HeaderValue::from_str(this.request_id) {
+                    response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                }
+
+                let message = format!("{} {} {}", method, route, status.as_u16());
+                if status.is_server_error() {
+                    this.logger.error_with_attrs(&message, attributes);
+                } else {
+                    this.logger.info_with_attrs(&message, attributes);
+                }
+                Poll::Ready(Ok(response))
+            }
+            Err(e) => {
+                attributes.push(Attribute::new("error", e.to_string()));
+                this.logger
+                    .error_with_attrs(&format!("{} {} failed", method, route), attributes);
+                Poll::Ready(Err(e))
+            }
+        }
+    }
+}
+
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+fn traceparent_trace_id(traceparent: &str) -> Option<String> {
+    let mut parts = traceparent.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let parent_id = parts.next()?;
+    let _flags = parts.next()?;
+
+    let is_hex = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_hexdigit());
+    if !is_hex(version, 2) || version.eq_ignore_ascii_case("ff") {
+        return None;
+    }
+    if !is_hex(trace_id, 32) || trace_id.chars().all(|c| c == '0') {
+        return None;
+    }
+    if !is_hex(parent_id, 16) {
+        return None;
+    }
+    Some(trace_id.to_ascii_lowercase())
+}
+
+fn attribute_value(attributes: &[Attribute], key: &str) -> String {
+    attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.clone())
+        .unwrap_or_default()
+}
+
+fn response_size<B: Body>(response: &Response<B>) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .or_else(|| response.body().size_hint().exact())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_ids_are_bounded() {
+        assert!(is_valid_request_id("req-42_a.b"));
+        assert!(is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN)));
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+        assert!(!is_valid_request_id("id with spaces"));
+        assert!(!is_valid_request_id("id\",\"admin"));
+    }
+
+    #[test]
+    fn traceparent_yields_a_valid_trace_id() {
+        assert_eq!(
+            traceparent_trace_id("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01")
+                .as_deref(),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+        for header in [
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "garbage",
+        ] {
+            assert_eq!(traceparent_trace_id(header), None, "{}", header);
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use crate::types::{Attribute, LogLevel};
-use crate::{logger::Logger as VigilantLogger, TracingAdapterBuilder};
+use crate::types::{Attribute, LogLevel, MetricType};
+#[allow(deprecated)]
+use crate::TracingAdapterBuilder;
+use crate::{logger::Logger as VigilantLogger, Metrics, TracingLayerBuilder};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::field::Field;
 use tracing::level_filters::LevelFilter;
 use tracing::span::{Attributes as TracingAttributes, Id};
@@ -19,20 +22,54 @@ pub struct TracingAdapter {
 
 struct TracingAdapterInner {
     vigilant_logger: VigilantLogger,
+    metrics: Metrics,
     level_filter: LevelFilter,
+    span_durations: bool,
 }
 
+const METRIC_PREFIXES: [(&str, MetricType); 4] = [
+    ("counter.", MetricType::Counter),
+    ("monotonic_counter.", MetricType::Counter),
+    ("histogram.", MetricType::Histogram),
+    ("gauge.", MetricType::Gauge),
+];
+
+struct SpanStart(Instant);
+
 impl TracingAdapter {
     pub fn new(vigilant_logger: VigilantLogger, level_filter: LevelFilter) -> Self {
+        Self::with_span_durations(vigilant_logger, level_filter, false)
+    }
+
+    pub(crate) fn with_span_durations(
+        vigilant_logger: VigilantLogger,
+        level_filter: LevelFilter,
+        span_durations: bool,
+    ) -> Self {
+        let metrics = vigilant_logger.metrics();
         Self {
             inner: Arc::new(TracingAdapterInner {
                 vigilant_logger,
+                metrics,
                 level_filter,
+                span_durations,
             }),
         }
     }
 
-    pub fn builder<'a>() -> TracingAdapterBuilder<'a> {
+    pub fn from_logger(vigilant_logger: VigilantLogger) -> Self {
+        TracingLayerBuilder::new(vigilant_logger).build()
+    }
+
+    pub fn layer_builder(vigilant_logger: VigilantLogger) -> TracingLayerBuilder {
+        TracingLayerBuilder::new(vigilant_logger)
+    }
+
+    #[deprecated(
+        note = "use `LoggerBuilder::tracing_layer` or `TracingAdapter::layer_builder` instead"
+    )]
+    #[allow(deprecated)]
+    pub fn builder() -> TracingAdapterBuilder {
         TracingAdapterBuilder::new()
     }
 
@@ -64,6 +101,11 @@ where
         let mut visitor = AllFieldsVisitor(&mut fields_map);
         event.record(&mut visitor);
 
+        let has_metrics = self.record_metrics(&mut fields_map);
+        if has_metrics && !fields_map.contains_key("message") {
+            return;
+        }
+
         let mut attributes: Vec<Attribute> = fields_map
             .into_iter()
             .map(|(k, v)| Attribute::new(k, v))
@@ -79,6 +121,15 @@ where
         if let Some(module_path) = metadata.module_path() {
             attributes.push(Attribute::new("module_path", module_path));
         }
+        if let Some(root) = ctx
+            .event_scope(event)
+            .and_then(|scope| scope.from_root().next())
+        {
+            attributes.push(Attribute::new(
+                "trace.id",
+                format!("{:x}", root.id().into_u64()),
+            ));
+        }
         if let Some(current_span) = ctx.current_span().id() {
             if event.parent().is_none() {
                 attributes.push(Attribute::new(
@@ -111,11 +162,26 @@ where
             LogLevel::WARNING => logger.warn_with_attrs(&message, attributes),
             LogLevel::INFO => logger.info_with_attrs(&message, attributes),
             LogLevel::DEBUG => logger.debug_with_attrs(&message, attributes),
+            LogLevel::FATAL => logger.fatal_with_attrs(&message, attributes),
         }
     }
 
-    fn on_new_span(&self, attrs: &TracingAttributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+    fn on_new_span(&self, attrs: &TracingAttributes<'_>, id: &Id, ctx: Context<'_, S>) {
         let logger = &self.inner.vigilant_logger;
+
+        if !self
+            .inner
+            .level_filter
+            .enabled(attrs.metadata(), ctx.clone())
+        {
+            return;
+        }
+
+        if self.inner.span_durations {
+            if let Some(span) = ctx.span(id) {
+                span.extensions_mut().insert(SpanStart(Instant::now()));
+            }
+        }
         let mut attributes = Vec::new();
 
         if let Some(parent) = attrs.parent() {
@@ -140,6 +206,77 @@ where
 
         logger.info_with_attrs(&message, attributes);
     }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+
+        if span.parent().is_none() {
+            self.inner
+                .vigilant_logger
+                .discard_buffered(&format!("{:x}", id.into_u64()));
+        }
+
+        if self.inner.span_durations
+            && self
+                .inner
+                .level_filter
+                .enabled(span.metadata(), ctx.clone())
+        {
+            if let Some(SpanStart(start)) = span.extensions().get::<SpanStart>() {
+                self.inner
+                    .metrics
+                    .histogram("span.duration_ms")
+                    .record_with_attrs(
+                        start.elapsed().as_secs_f64() * 1000.0,
+                        vec![Attribute::new("span.name", span.metadata().name())],
+                    );
+            }
+        }
+    }
+}
+
+impl TracingAdapter {
+    fn record_metrics(&self, fields_map: &mut HashMap<String, String>) -> bool {
+        let mut recorded = Vec::new();
+        for (prefix, metric_type) in METRIC_PREFIXES {
+            let keys: Vec<String> = fields_map
+                .keys()
+                .filter(|k| k.starts_with(prefix))
+                .cloned()
+                .collect();
+            for key in keys {
+                if let Some(value) = fields_map.remove(&key) {
+                    recorded.push((key[prefix.len()..].to_string(), metric_type, value));
+                }
+            }
+        }
+
+        if recorded.is_empty() {
+            return false;
+        }
+
+        let attributes: Vec<Attribute> = fields_map
+            .iter()
+            .filter(|(k, _)| k.as_str() != "message")
+            .map(|(k, v)| Attribute::new(k.clone(), v.clone()))
+            .collect();
+
+        let metrics = &self.inner.metrics;
+        for (name, metric_type, value) in recorded {
+            let Ok(value) = value.parse::<f64>() else {
+                continue;
+            };
+            let attrs = attributes.clone();
+            match metric_type {
+                MetricType::Counter => metrics.counter(name).add_with_attrs(value, attrs),
+                MetricType::Gauge => metrics.gauge(name).set_with_attrs(value, attrs),
+                MetricType::Histogram => metrics.histogram(name).record_with_attrs(value, attrs),
+            }
+        }
+        true
+    }
 }
 
 struct AllFieldsVisitor<'a>(&'a mut HashMap<String, String>);
@@ -1,56 +1,118 @@
+use crate::config::{ConfigOverrides, VigilantConfig};
+use crate::error::VigilantError;
+use crate::logger::Logger as VigilantLogger;
 use crate::{LoggerBuilder, TracingAdapter};
 use tracing::level_filters::LevelFilter;
 
-pub struct TracingAdapterBuilder<'a> {
-    name: &'a str,
-    endpoint: &'a str,
-    token: &'a str,
-    passthrough: bool,
-    insecure: bool,
-    noop: bool,
+pub struct TracingLayerBuilder {
+    vigilant_logger: VigilantLogger,
     level_filter: LevelFilter,
+    span_durations: bool,
 }
 
-impl<'a> TracingAdapterBuilder<'a> {
+impl TracingLayerBuilder {
+    pub fn new(vigilant_logger: VigilantLogger) -> Self {
+        let level_filter = LevelFilter::from(vigilant_logger.level()).min(LevelFilter::INFO);
+        Self {
+            vigilant_logger,
+            level_filter,
+            span_durations: false,
+        }
+    }
+
+    pub fn level_filter(mut self, level_filter: LevelFilter) -> Self {
+        self.level_filter = level_filter;
+        self
+    }
+
+    pub fn span_durations(mut self, enabled: bool) -> Self {
+        self.span_durations = enabled;
+        self
+    }
+
+    pub fn build(self) -> TracingAdapter {
+        TracingAdapter::with_span_durations(
+            self.vigilant_logger,
+            self.level_filter,
+            self.span_durations,
+        )
+    }
+}
+
+#[deprecated(
+    note = "use `LoggerBuilder::tracing_layer` or `TracingAdapter::layer_builder` instead"
+)]
+pub struct TracingAdapterBuilder {
+    logger_builder: LoggerBuilder,
+    level_filter: LevelFilter,
+    span_durations: bool,
+}
+
+#[allow(deprecated)]
+impl Default for TracingAdapterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(deprecated)]
+impl TracingAdapterBuilder {
+    pub fn from_env() -> Self {
+        let mut builder = Self::from_logger_builder(LoggerBuilder::from_env());
+        if let Some(level) = ConfigOverrides::from_env().level {
+            builder.level_filter = level.into();
+        }
+        builder
+    }
+
+    pub fn from_config(config: VigilantConfig) -> Self {
+        Self::from_logger_builder(LoggerBuilder::from_config(config))
+    }
+
     pub fn new() -> Self {
+        Self::from_logger_builder(LoggerBuilder::new())
+    }
+
+    fn from_logger_builder(logger_builder: LoggerBuilder) -> Self {
         Self {
-            name: "sample-app",
-            endpoint: "ingress.vigilant.run",
-            token: "tk_1234567890",
-            passthrough: false,
-            insecure: false,
-            noop: false,
+            logger_builder,
             level_filter: LevelFilter::INFO,
+            span_durations: false,
         }
     }
 
-    pub fn name(mut self, name: &'a str) -> Self {
-        self.name = name;
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.logger_builder = self.logger_builder.name(name);
         self
     }
 
-    pub fn endpoint(mut self, endpoint: &'a str) -> Self {
-        self.endpoint = endpoint;
+    pub fn endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.logger_builder = self.logger_builder.endpoint(endpoint);
         self
     }
 
-    pub fn token(mut self, token: &'a str) -> Self {
-        self.token = token;
+    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+        self.logger_builder = self.logger_builder.token(token);
+        self
+    }
+
+    pub fn dsn(mut self, dsn: &str) -> Self {
+        self.logger_builder = self.logger_builder.dsn(dsn);
         self
     }
 
     pub fn passthrough(mut self, enabled: bool) -> Self {
-        self.passthrough = enabled;
+        self.logger_builder = self.logger_builder.passthrough(enabled);
         self
     }
 
     pub fn insecure(mut self, enabled: bool) -> Self {
-        self.insecure = enabled;
+        self.logger_builder = self.logger_builder.insecure(enabled);
         self
     }
 
     pub fn noop(mut self, enabled: bool) -> Self {
-        self.noop = enabled;
+        self.logger_builder = self.logger_builder.noop(enabled);
         self
     }
 
@@ -59,16 +121,22 @@ impl<'a> TracingAdapterBuilder<'a> {
         self
     }
 
+    pub fn span_durations(mut self, enabled: bool) -> Self {
+        self.span_durations = enabled;
+        self
+    }
+
     pub fn build(self) -> TracingAdapter {
-        let vigilant_logger = LoggerBuilder::new()
-            .name(self.name)
-            .endpoint(self.endpoint)
-            .token(self.token)
-            .passthrough(self.passthrough)
-            .insecure(self.insecure)
-            .noop(self.noop)
-            .build();
-
-        TracingAdapter::new(vigilant_logger, self.level_filter)
+        TracingLayerBuilder::new(self.logger_builder.build())
+            .level_filter(self.level_filter)
+            .span_durations(self.span_durations)
+            .build()
+    }
+
+    pub fn try_build(self) -> Result<TracingAdapter, VigilantError> {
+        Ok(TracingLayerBuilder::new(self.logger_builder.try_build()?)
+            .level_filter(self.level_filter)
+            .span_durations(self.span_durations)
+            .build())
     }
 }
@@ -1,4 +1,12 @@
 pub mod adapter_env_logger;
 pub mod adapter_env_logger_builder;
+#[cfg(feature = "reqwest-middleware")]
+pub mod adapter_reqwest;
+#[cfg(feature = "slog")]
+pub mod adapter_slog;
+#[cfg(feature = "slog")]
+pub mod adapter_slog_builder;
+#[cfg(feature = "tower")]
+pub mod adapter_tower;
 pub mod adapter_tracing;
 pub mod adapter_tracing_builder;
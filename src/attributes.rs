@@ -0,0 +1,113 @@
+use crate::types::Attribute;
+use serde::Serialize;
+use serde_json::Value;
+
+pub trait ToAttributes {
+    fn write_attributes(&self, prefix: &str, attributes: &mut Vec<Attribute>);
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        let mut attributes = Vec::new();
+        self.write_attributes("", &mut attributes);
+        attributes
+    }
+}
+
+impl<T: ToAttributes + ?Sized> ToAttributes for &T {
+    fn write_attributes(&self, prefix: &str, attributes: &mut Vec<Attribute>) {
+        (**self).write_attributes(prefix, attributes);
+    }
+}
+
+impl<T: ToAttributes> ToAttributes for Option<T> {
+    fn write_attributes(&self, prefix: &str, attributes: &mut Vec<Attribute>) {
+        if let Some(value) = self {
+            value.write_attributes(prefix, attributes);
+        }
+    }
+}
+
+pub fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+const MAX_DEPTH: usize = 8;
+const MAX_ATTRIBUTES: usize = 128;
+const MAX_VALUE_LEN: usize = 1024;
+
+impl Attribute {
+    pub fn from_serialize<T: Serialize + ?Sized>(key: &str, value: &T) -> Vec<Attribute> {
+        let value = match serde_json::to_value(value) {
+            Ok(value) => value,
+            Err(e) => return vec![Attribute::new(key, format!("<unserializable: {}>", e))],
+        };
+
+        let mut attributes = Vec::new();
+        let mut truncated = false;
+        flatten_value(key, &value, 0, &mut attributes, &mut truncated);
+        if truncated {
+            attributes.push(Attribute::new(join_key(key, "truncated"), "true"));
+        }
+        attributes
+    }
+}
+
+fn flatten_value(
+    key: &str,
+    value: &Value,
+    depth: usize,
+    attributes: &mut Vec<Attribute>,
+    truncated: &mut bool,
+) {
+    if attributes.len() >= MAX_ATTRIBUTES {
+        *truncated = true;
+        return;
+    }
+
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) if !map.is_empty() => {
+            map.iter().map(|(k, v)| (join_key(key, k), v)).collect()
+        }
+        Value::Array(items) if !items.is_empty() => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (join_key(key, &i.to_string()), v))
+            .collect(),
+        Value::String(s) => {
+            attributes.push(Attribute::new(key, limit_len(s, truncated)));
+            return;
+        }
+        other => {
+            attributes.push(Attribute::new(
+                key,
+                limit_len(&other.to_string(), truncated),
+            ));
+            return;
+        }
+    };
+
+    if depth >= MAX_DEPTH {
+        *truncated = true;
+        attributes.push(Attribute::new(
+            key,
+            limit_len(&value.to_string(), truncated),
+        ));
+        return;
+    }
+    for (child_key, child) in children {
+        flatten_value(&child_key, child, depth + 1, attributes, truncated);
+    }
+}
+
+fn limit_len(value: &str, truncated: &mut bool) -> String {
+    match value.char_indices().nth(MAX_VALUE_LEN) {
+        Some((end, _)) => {
+            *truncated = true;
+            format!("{}...", &value[..end])
+        }
+        None => value.to_string(),
+    }
+}
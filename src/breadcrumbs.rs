@@ -0,0 +1,64 @@
+use crate::redaction::Redactor;
+use crate::types::{Attribute, LogLevel};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+thread_local! {
+    static BREADCRUMBS: RefCell<HashMap<u64, VecDeque<Breadcrumb>>> =
+        RefCell::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Breadcrumb {
+    pub(crate) timestamp: String,
+    pub(crate) level: LogLevel,
+    pub(crate) message: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) data: BTreeMap<String, String>,
+}
+
+impl Breadcrumb {
+    pub(crate) fn new(
+        timestamp: String,
+        level: LogLevel,
+        message: &str,
+        data: impl IntoIterator<Item = Attribute>,
+    ) -> Self {
+        Self {
+            timestamp,
+            level,
+            message: message.to_string(),
+            data: data
+                .into_iter()
+                .map(|attr| (attr.key, attr.value))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn redact(&mut self, redactor: &Redactor) {
+        self.message = redactor.redact_value(&self.message);
+        for (key, value) in self.data.iter_mut() {
+            *value = redactor.redact_attribute(key, value);
+        }
+    }
+}
+
+pub(crate) fn record(logger: u64, capacity: usize, breadcrumb: Breadcrumb) {
+    BREADCRUMBS.with(|breadcrumbs| {
+        let mut breadcrumbs = breadcrumbs.borrow_mut();
+        let ring = breadcrumbs.entry(logger).or_default();
+        if ring.len() >= capacity {
+            ring.pop_front();
+        }
+        ring.push_back(breadcrumb);
+    });
+}
+
+pub(crate) fn to_json(logger: u64) -> Option<String> {
+    BREADCRUMBS.with(|breadcrumbs| {
+        let breadcrumbs = breadcrumbs.borrow();
+        let ring = breadcrumbs.get(&logger).filter(|ring| !ring.is_empty())?;
+        serde_json::to_string(ring).ok()
+    })
+}
@@ -0,0 +1,307 @@
+use crate::types::Attribute;
+use crate::util::fnv1a64;
+use serde::Serialize;
+use std::backtrace::Backtrace;
+use std::error::Error;
+
+const MAX_FRAMES: usize = 50;
+const FINGERPRINT_FRAMES: usize = 3;
+const RUNTIME_CRATES: [&str; 4] = ["std", "core", "alloc", "vigilant"];
+
+#[derive(Debug, Serialize)]
+struct Frame {
+    function: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    in_app: bool,
+}
+
+pub(crate) fn error_attributes<E: Error + ?Sized>(
+    err: &E,
+    app_crates: &[String],
+) -> Vec<Attribute> {
+    let type_name = std::any::type_name::<E>();
+    let mut chain = Vec::new();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        chain.push(cause.to_string());
+        source = cause.source();
+    }
+
+    let frames = parse_frames(&Backtrace::force_capture().to_string(), app_crates);
+    let fingerprint = fingerprint(type_name, &frames);
+
+    vec![
+        Attribute::new("event.type", "error"),
+        Attribute::new("error.type", type_name),
+        Attribute::new("error.message", err.to_string()),
+        Attribute::new(
+            "error.chain",
+            serde_json::to_string(&chain).unwrap_or_default(),
+        ),
+        Attribute::new(
+            "error.frames",
+            serde_json::to_string(&frames).unwrap_or_default(),
+        ),
+        Attribute::new("error.fingerprint", fingerprint),
+    ]
+}
+
+fn parse_frames(backtrace: &str, app_crates: &[String]) -> Vec<Frame> {
+    let mut frames: Vec<Frame> = Vec::new();
+    for line in backtrace.lines() {
+        let line = line.trim();
+        if let Some(location) = line.strip_prefix("at ") {
+            if let Some(frame) = frames.last_mut() {
+                let (file, line) = split_location(location);
+                frame.file = Some(file);
+                frame.line = line;
+            }
+            continue;
+        }
+
+        let Some((index, function)) = line.split_once(": ") else {
+            continue;
+        };
+        if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        frames.push(Frame {
+            function: strip_hash(function).to_string(),
+            file: None,
+            line: None,
+            in_app: false,
+        });
+    }
+
+    // Drop the frames of the capture itself: everything up to the end of the
+    // first run of this crate's frames.
+    if let Some(first) = frames.iter().position(is_capture_frame) {
+        let end = frames[first..]
+            .iter()
+            .position(|frame| !is_capture_frame(frame))
+            .map_or(frames.len(), |offset| first + offset);
+        frames.drain(..end);
+    }
+
+    let app_crates: Vec<String> = if app_crates.is_empty() {
+        infer_app_crate(&frames)
+            .map(str::to_string)
+            .into_iter()
+            .collect()
+    } else {
+        app_crates.to_vec()
+    };
+    for frame in &mut frames {
+        frame.in_app = crate_name(&frame.function)
+            .is_some_and(|name| app_crates.iter().any(|app| app == name));
+    }
+    frames.truncate(MAX_FRAMES);
+    frames
+}
+
+fn is_capture_frame(frame: &Frame) -> bool {
+    crate_name(&frame.function) == Some("vigilant")
+}
+
+fn infer_app_crate(frames: &[Frame]) -> Option<&str> {
+    frames
+        .iter()
+        .find_map(|frame| {
+            let (name, rest) = qualified_path(&frame.function).split_once("::")?;
+            (rest == "main" || rest.starts_with("main::")).then_some(name)
+        })
+        .or_else(|| {
+            frames
+                .iter()
+                .filter_map(|frame| crate_name(&frame.function))
+                .find(|name| !RUNTIME_CRATES.contains(name))
+        })
+}
+
+fn crate_name(function: &str) -> Option<&str> {
+    let (name, _) = qualified_path(function).split_once("::")?;
+    (!name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')).then_some(name)
+}
+
+fn split_location(location: &str) -> (String, Option<u32>) {
+    let mut parts = location.rsplitn(3, ':');
+    let _column = parts.next();
+    let line = parts.next().and_then(|line| line.parse().ok());
+    match (line, parts.next()) {
+        (Some(line), Some(file)) => (file.to_string(), Some(line)),
+        _ => (location.to_string(), None),
+    }
+}
+
+fn strip_hash(function: &str) -> &str {
+    match function.rsplit_once("::h") {
+        Some((name, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            name
+        }
+        _ => function,
+    }
+}
+
+fn fingerprint(type_name: &str, frames: &[Frame]) -> String {
+    let app_frames: Vec<&str> = frames
+        .iter()
+        .filter(|frame| frame.in_app)
+        .take(FINGERPRINT_FRAMES)
+        .map(|frame| frame.function.as_str())
+        .collect();
+
+    let mut key = type_name.to_string();
+    for frame in app_frames {
+        key.push('\n');
+        key.push_str(frame);
+    }
+    format!("{:016x}", fnv1a64(key.as_bytes()))
+}
+
+fn qualified_path(function: &str) -> &str {
+    let mut path = function;
+    loop {
+        let trimmed = path
+            .trim_start_matches(['<', '&', ' '])
+            .trim_start_matches("mut ")
+            .trim_start_matches("dyn ");
+        if trimmed.len() == path.len() {
+            return path;
+        }
+        path = trimmed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BACKTRACE: &str = "\
+   0: std::backtrace::Backtrace::force_capture
+             at /rustc/abc/library/std/src/backtrace.rs:312:9
+   1: vigilant::capture::error_attributes
+             at ./src/capture.rs:40:32
+   2: vigilant::logger::Logger::capture_error::h0123456789abcdef
+             at ./src/logger.rs:338:25
+   3: hyper::proto::h1::dispatch::Dispatcher::poll_catch
+   4: my_app::handlers::load::h0123456789abcdef
+             at ./src/handlers.rs:12:5
+   5: <my_app::Job as core::ops::FnOnce<()>>::call_once
+             at ./src/job.rs:7:9
+   6: core::ops::function::FnOnce::call_once
+   7: my_app::main
+             at ./src/main.rs:3:5
+   8: main
+";
+
+    fn frame(function: &str, in_app: bool) -> Frame {
+        Frame {
+            function: function.to_string(),
+            file: None,
+            line: None,
+            in_app,
+        }
+    }
+
+    #[test]
+    fn split_location_separates_file_and_line() {
+        assert_eq!(
+            split_location("./src/main.rs:12:5"),
+            ("./src/main.rs".to_string(), Some(12))
+        );
+        assert_eq!(
+            split_location("C:\\app\\src\\main.rs:3:1"),
+            ("C:\\app\\src\\main.rs".to_string(), Some(3))
+        );
+        assert_eq!(split_location("<unknown>"), ("<unknown>".to_string(), None));
+    }
+
+    #[test]
+    fn strip_hash_removes_symbol_hashes_only() {
+        assert_eq!(strip_hash("my_app::run::h0123456789abcdef"), "my_app::run");
+        assert_eq!(strip_hash("my_app::handler"), "my_app::handler");
+        assert_eq!(strip_hash("my_app::hash::hxyz"), "my_app::hash::hxyz");
+    }
+
+    #[test]
+    fn parse_frames_drops_capture_frames_and_infers_the_app_crate() {
+        let frames = parse_frames(BACKTRACE, &[]);
+        let summary: Vec<(&str, bool)> = frames
+            .iter()
+            .map(|frame| (frame.function.as_str(), frame.in_app))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("hyper::proto::h1::dispatch::Dispatcher::poll_catch", false),
+                ("my_app::handlers::load", true),
+                ("<my_app::Job as core::ops::FnOnce<()>>::call_once", true),
+                ("core::ops::function::FnOnce::call_once", false),
+                ("my_app::main", true),
+                ("main", false),
+            ]
+        );
+        assert_eq!(frames[1].file.as_deref(), Some("./src/handlers.rs"));
+        assert_eq!(frames[1].line, Some(12));
+        assert_eq!(frames[0].file, None);
+    }
+
+    #[test]
+    fn parse_frames_uses_configured_app_crates() {
+        let frames = parse_frames(BACKTRACE, &["hyper".to_string()]);
+        assert!(frames[0].in_app);
+        assert!(!frames[1].in_app);
+    }
+
+    #[test]
+    fn inferred_crate_falls_back_to_the_first_caller() {
+        let frames = [
+            frame("core::ops::function::FnOnce::call_once", false),
+            frame("worker::job::run", false),
+            frame("std::thread::spawn", false),
+        ];
+        assert_eq!(infer_app_crate(&frames), Some("worker"));
+    }
+
+    #[test]
+    fn fingerprint_uses_type_and_top_in_app_frames() {
+        let frames = [
+            frame("tokio::runtime::poll", false),
+            frame("my_app::a", true),
+            frame("my_app::b", true),
+            frame("my_app::c", true),
+            frame("my_app::d", true),
+        ];
+        let base = fingerprint("my_app::Error", &frames);
+        assert_eq!(base.len(), 16);
+        assert_eq!(fingerprint("my_app::Error", &frames), base);
+        assert_ne!(fingerprint("other::Error", &frames), base);
+
+        let mut upgraded = frames;
+        upgraded[0] = frame("tokio::runtime::scheduler::poll", false);
+        upgraded[4] = frame("my_app::e", true);
+        assert_eq!(fingerprint("my_app::Error", &upgraded), base);
+    }
+
+    #[test]
+    fn fingerprint_without_app_frames_uses_the_type() {
+        assert_eq!(
+            fingerprint("my_app::Error", &[frame("std::rt::lang_start", false)]),
+            fingerprint("my_app::Error", &[])
+        );
+    }
+
+    #[test]
+    fn error_type_is_the_static_type_name() {
+        let err = "x".parse::<u32>().unwrap_err();
+        let attrs = error_attributes(&err, &[]);
+        assert_eq!(attrs[1].value, "core::num::error::ParseIntError");
+
+        let boxed: Box<dyn Error> = Box::new(std::io::Error::other("boom"));
+        let attrs = error_attributes(&*boxed, &[]);
+        assert_eq!(attrs[1].value, "dyn core::error::Error");
+    }
+}
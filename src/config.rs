@@ -0,0 +1,523 @@
+use crate::error::VigilantError;
+use crate::types::LogLevel;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::env;
+use std::time::Duration;
+
+const PLACEHOLDER_TOKEN: &str = "tk_1234567890";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VigilantConfig {
+    pub name: String,
+    pub endpoint: String,
+    pub token: String,
+    pub passthrough: bool,
+    pub insecure: bool,
+    pub noop: bool,
+    #[serde(deserialize_with = "deserialize_level")]
+    pub level: LogLevel,
+    pub max_batch_size: usize,
+    pub batch_interval_ms: u64,
+    pub metrics_interval_ms: u64,
+}
+
+impl Default for VigilantConfig {
+    fn default() -> Self {
+        Self {
+            name: "sample-app".to_string(),
+            endpoint: "ingress.vigilant.run".to_string(),
+            token: PLACEHOLDER_TOKEN.to_string(),
+            passthrough: false,
+            insecure: false,
+            noop: false,
+            level: LogLevel::DEBUG,
+            max_batch_size: 100,
+            batch_interval_ms: 100,
+            metrics_interval_ms: 10_000,
+        }
+    }
+}
+
+impl VigilantConfig {
+    pub fn from_env() -> Self {
+        let overrides = ConfigOverrides::from_env();
+        for e in &overrides.errors {
+            eprintln!("Ignoring {}", e);
+        }
+        let mut config = Self::default();
+        config.apply(overrides);
+        config
+    }
+
+    pub fn from_dsn(dsn: &str) -> Result<Self, VigilantError> {
+        let mut config = Self::default();
+        config.apply(ConfigOverrides::from_dsn(dsn)?);
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), VigilantError> {
+        let secret = self.token.strip_prefix("tk_").ok_or_else(|| {
+            VigilantError::InvalidToken("token must start with \"tk_\"".to_string())
+        })?;
+        if secret.is_empty() {
+            return Err(VigilantError::InvalidToken(
+                "token must not be empty".to_string(),
+            ));
+        }
+        if !secret
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(VigilantError::InvalidToken(
+                "token contains invalid characters".to_string(),
+            ));
+        }
+        if self.token == PLACEHOLDER_TOKEN && !self.noop {
+            return Err(VigilantError::InvalidToken("token is not set".to_string()));
+        }
+
+        if self.endpoint.trim().is_empty() {
+            return Err(VigilantError::InvalidEndpoint(
+                "endpoint must not be empty".to_string(),
+            ));
+        }
+        let malformed = || {
+            VigilantError::InvalidEndpoint(format!(
+                "expected a host with an optional port, got \"{}\"",
+                self.endpoint
+            ))
+        };
+        if self.endpoint.contains("://") || self.endpoint.contains(['/', '?', '#', '@']) {
+            return Err(malformed());
+        }
+        let url = reqwest::Url::parse(&self.formatted_endpoint())
+            .map_err(|e| VigilantError::InvalidEndpoint(e.to_string()))?;
+        let host = url.host_str().unwrap_or_default();
+        if host.is_empty() {
+            return Err(VigilantError::InvalidEndpoint(
+                "endpoint has no host".to_string(),
+            ));
+        }
+        let matches = |port: Option<u16>| {
+            let parsed = match port {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            };
+            self.endpoint.eq_ignore_ascii_case(&parsed)
+        };
+        if !matches(url.port()) && !matches(url.port_or_known_default()) {
+            return Err(malformed());
+        }
+
+        if self.max_batch_size == 0 {
+            return Err(VigilantError::InvalidBatchConfig(
+                "max_batch_size must be greater than zero".to_string(),
+            ));
+        }
+        if self.batch_interval_ms == 0 {
+            return Err(VigilantError::InvalidBatchConfig(
+                "batch_interval_ms must be greater than zero".to_string(),
+            ));
+        }
+        if self.metrics_interval_ms == 0 {
+            return Err(VigilantError::InvalidBatchConfig(
+                "metrics_interval_ms must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn formatted_endpoint(&self) -> String {
+        if self.insecure {
+            format!("http://{}/api/message", self.endpoint)
+        } else {
+            format!("https://{}/api/message", self.endpoint)
+        }
+    }
+
+    pub(crate) fn apply(&mut self, overrides: ConfigOverrides) {
+        if let Some(name) = overrides.name {
+            self.name = name;
+        }
+        if let Some(endpoint) = overrides.endpoint {
+            self.endpoint = endpoint;
+        }
+        if let Some(token) = overrides.token {
+            self.token = token;
+        }
+        if let Some(passthrough) = overrides.passthrough {
+            self.passthrough = passthrough;
+        }
+        if let Some(insecure) = overrides.insecure {
+            self.insecure = insecure;
+        }
+        if let Some(noop) = overrides.noop {
+            self.noop = noop;
+        }
+        if let Some(level) = overrides.level {
+            self.level = level;
+        }
+        if let Some(interval) = overrides.metrics_interval {
+            self.metrics_interval_ms = interval.as_millis() as u64;
+        }
+    }
+}
+
+fn deserialize_level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LogLevel, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+pub(crate) struct ConfigOverrides {
+    pub(crate) name: Option<String>,
+    pub(crate) endpoint: Option<String>,
+    pub(crate) token: Option<String>,
+    pub(crate) passthrough: Option<bool>,
+    pub(crate) insecure: Option<bool>,
+    pub(crate) noop: Option<bool>,
+    pub(crate) level: Option<LogLevel>,
+    pub(crate) metrics_interval: Option<Duration>,
+    pub(crate) errors: Vec<VigilantError>,
+}
+
+impl ConfigOverrides {
+    pub(crate) fn from_env() -> Self {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let env_string = |key: &str| var(key).filter(|value| !value.is_empty());
+        let env_bool = |key: &str, errors: &mut Vec<VigilantError>| {
+            let value = env_string(key)?;
+            match value.to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => Some(true),
+                "0" | "false" | "no" | "off" => Some(false),
+                _ => {
+                    errors.push(VigilantError::InvalidEnv(format!(
+                        "{}: expected a boolean, got \"{}\"",
+                        key, value
+                    )));
+                    None
+                }
+            }
+        };
+        let mut errors = Vec::new();
+        let mut config = match var("VIGILANT_DSN") {
+            Some(dsn) => Self::from_dsn(&dsn).unwrap_or_else(|e| {
+                errors.push(VigilantError::InvalidEnv(format!("VIGILANT_DSN: {}", e)));
+                Self::empty()
+            }),
+            None => Self::empty(),
+        };
+
+        if let Some(name) = env_string("VIGILANT_SERVICE_NAME") {
+            config.name = Some(name);
+        }
+        if let Some(endpoint) = env_string("VIGILANT_ENDPOINT") {
+            config.endpoint = Some(endpoint);
+        }
+        if let Some(token) = env_string("VIGILANT_TOKEN") {
+            config.token = Some(token);
+        }
+        if let Some(passthrough) = env_bool("VIGILANT_PASSTHROUGH", &mut errors) {
+            config.passthrough = Some(passthrough);
+        }
+        if let Some(insecure) = env_bool("VIGILANT_INSECURE", &mut errors) {
+            config.insecure = Some(insecure);
+        }
+        if let Some(noop) = env_bool("VIGILANT_NOOP", &mut errors) {
+            config.noop = Some(noop);
+        }
+        if let Some(level) = env_string("VIGILANT_LEVEL") {
+            match level.parse() {
+                Ok(level) => config.level = Some(level),
+                Err(_) => errors.push(VigilantError::InvalidEnv(format!(
+                    "VIGILANT_LEVEL: unknown level \"{}\"",
+                    level
+                ))),
+            }
+        }
+        if let Some(secs) = env_string("VIGILANT_METRICS_INTERVAL") {
+            match secs.parse::<u64>() {
+                Ok(secs) if secs > 0 => config.metrics_interval = Some(Duration::from_secs(secs)),
+                _ => errors.push(VigilantError::InvalidEnv(format!(
+                    "VIGILANT_METRICS_INTERVAL: expected a positive number of seconds, got \"{}\"",
+                    secs
+                ))),
+            }
+        }
+
+        config.errors = errors;
+        config
+    }
+
+    pub(crate) fn from_dsn(dsn: &str) -> Result<Self, VigilantError> {
+        let url = reqwest::Url::parse(dsn).map_err(|e| VigilantError::InvalidDsn(e.to_string()))?;
+
+        let insecure = match url.scheme() {
+            "https" => false,
+            "http" => true,
+            scheme => {
+                return Err(VigilantError::InvalidDsn(format!(
+                    "unsupported scheme \"{}\"",
+                    scheme
+                )))
+            }
+        };
+
+        let token = url.username();
+        if token.is_empty() {
+            return Err(VigilantError::InvalidDsn("missing token".to_string()));
+        }
+
+        let host = url
+            .host_str()
+            .ok_or_else(|| VigilantError::InvalidDsn("missing host".to_string()))?;
+        let endpoint = match url.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+
+        let mut segments = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty());
+        let name = segments.next().map(|segment| segment.to_string());
+        if segments.next().is_some() {
+            return Err(VigilantError::InvalidDsn(format!(
+                "expected only a service name in the path, got \"{}\"",
+                url.path()
+            )));
+        }
+
+        Ok(Self {
+            name,
+            endpoint: Some(endpoint),
+            token: Some(token.to_string()),
+            insecure: Some(insecure),
+            ..Self::empty()
+        })
+    }
+
+    fn empty() -> Self {
+        Self {
+            name: None,
+            endpoint: None,
+            token: None,
+            passthrough: None,
+            insecure: None,
+            noop: None,
+            level: None,
+            metrics_interval: None,
+            errors: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(endpoint: &str, token: &str) -> VigilantConfig {
+        VigilantConfig {
+            endpoint: endpoint.to_string(),
+            token: token.to_string(),
+            ..VigilantConfig::default()
+        }
+    }
+
+    #[test]
+    fn accepts_hosts_with_optional_ports() {
+        for endpoint in [
+            "ingress.vigilant.run",
+            "Ingress.Vigilant.Run",
+            "localhost:8080",
+            "127.0.0.1:4000",
+            "[::1]:4000",
+            "example.com:443",
+        ] {
+            assert!(
+                config(endpoint, "tk_abc").validate().is_ok(),
+                "{}",
+                endpoint
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_endpoints() {
+        for endpoint in [
+            "",
+            "   ",
+            "https://example.com",
+            "example.com/api",
+            "host?x",
+            "host#frag",
+            "user@host",
+            "host:notaport",
+            " host",
+        ] {
+            assert!(
+                matches!(
+                    config(endpoint, "tk_abc").validate(),
+                    Err(VigilantError::InvalidEndpoint(_))
+                ),
+                "{:?}",
+                endpoint
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_tokens() {
+        for token in ["", "abc", "tk_", "tk_a b", "tk_a/b", PLACEHOLDER_TOKEN] {
+            assert!(
+                matches!(
+                    config("example.com", token).validate(),
+                    Err(VigilantError::InvalidToken(_))
+                ),
+                "{:?}",
+                token
+            );
+        }
+    }
+
+    #[test]
+    fn placeholder_token_is_allowed_for_noop_loggers() {
+        let config = VigilantConfig {
+            noop: true,
+            ..VigilantConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_zero_batch_parameters() {
+        let base = config("example.com", "tk_abc");
+        for config in [
+            VigilantConfig {
+                max_batch_size: 0,
+                ..base.clone()
+            },
+            VigilantConfig {
+                batch_interval_ms: 0,
+                ..base.clone()
+            },
+            VigilantConfig {
+                metrics_interval_ms: 0,
+                ..base.clone()
+            },
+        ] {
+            assert!(matches!(
+                config.validate(),
+                Err(VigilantError::InvalidBatchConfig(_))
+            ));
+        }
+    }
+
+    fn vars<'a>(pairs: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            pairs
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn dsn_sets_token_endpoint_name_and_scheme() {
+        let overrides = ConfigOverrides::from_dsn("https://tk_abc@ingress.example.com/checkout")
+            .expect("valid DSN");
+        assert_eq!(overrides.token.as_deref(), Some("tk_abc"));
+        assert_eq!(overrides.endpoint.as_deref(), Some("ingress.example.com"));
+        assert_eq!(overrides.name.as_deref(), Some("checkout"));
+        assert_eq!(overrides.insecure, Some(false));
+
+        let overrides =
+            ConfigOverrides::from_dsn("http://tk_abc@localhost:8080").expect("valid DSN");
+        assert_eq!(overrides.endpoint.as_deref(), Some("localhost:8080"));
+        assert_eq!(overrides.name, None);
+        assert_eq!(overrides.insecure, Some(true));
+    }
+
+    #[test]
+    fn dsn_allows_a_trailing_slash() {
+        let overrides =
+            ConfigOverrides::from_dsn("https://tk_abc@example.com/checkout/").expect("valid DSN");
+        assert_eq!(overrides.name.as_deref(), Some("checkout"));
+    }
+
+    #[test]
+    fn dsn_rejects_invalid_values() {
+        for dsn in [
+            "not a url",
+            "ftp://tk_abc@example.com/svc",
+            "https://example.com/svc",
+            "https://tk_abc@example.com/svc/extra",
+        ] {
+            assert!(
+                matches!(
+                    ConfigOverrides::from_dsn(dsn),
+                    Err(VigilantError::InvalidDsn(_))
+                ),
+                "{}",
+                dsn
+            );
+        }
+    }
+
+    #[test]
+    fn env_variables_take_precedence_over_the_dsn() {
+        let overrides = ConfigOverrides::from_vars(vars(&[
+            ("VIGILANT_DSN", "https://tk_dsn@dsn.example.com/from-dsn"),
+            ("VIGILANT_TOKEN", "tk_env"),
+            ("VIGILANT_SERVICE_NAME", "from-env"),
+            ("VIGILANT_INSECURE", "yes"),
+            ("VIGILANT_LEVEL", "warn"),
+            ("VIGILANT_METRICS_INTERVAL", "30"),
+        ]));
+        assert!(overrides.errors.is_empty());
+        assert_eq!(overrides.token.as_deref(), Some("tk_env"));
+        assert_eq!(overrides.name.as_deref(), Some("from-env"));
+        assert_eq!(overrides.endpoint.as_deref(), Some("dsn.example.com"));
+        assert_eq!(overrides.insecure, Some(true));
+        assert!(matches!(overrides.level, Some(LogLevel::WARNING)));
+        assert_eq!(overrides.metrics_interval, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn empty_env_variables_are_ignored() {
+        let overrides =
+            ConfigOverrides::from_vars(vars(&[("VIGILANT_TOKEN", ""), ("VIGILANT_NOOP", "")]));
+        assert!(overrides.errors.is_empty());
+        assert_eq!(overrides.token, None);
+        assert_eq!(overrides.noop, None);
+    }
+
+    #[test]
+    fn invalid_env_variables_are_reported() {
+        let overrides = ConfigOverrides::from_vars(vars(&[
+            ("VIGILANT_DSN", "https://example.com"),
+            ("VIGILANT_PASSTHROUGH", "maybe"),
+            ("VIGILANT_LEVEL", "loud"),
+            ("VIGILANT_METRICS_INTERVAL", "0"),
+        ]));
+        let errors: Vec<String> = overrides.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        for (error, key) in errors.iter().zip([
+            "VIGILANT_DSN",
+            "VIGILANT_PASSTHROUGH",
+            "VIGILANT_LEVEL",
+            "VIGILANT_METRICS_INTERVAL",
+        ]) {
+            assert!(error.contains(key), "{}", error);
+        }
+        assert_eq!(overrides.passthrough, None);
+        assert!(overrides.level.is_none());
+        assert_eq!(overrides.metrics_interval, None);
+    }
+}
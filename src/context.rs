@@ -0,0 +1,56 @@
+use crate::types::Attribute;
+use std::cell::RefCell;
+
+thread_local! {
+    static CONTEXT: RefCell<Vec<Vec<Attribute>>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    static TASK_CONTEXT: Vec<Attribute>;
+}
+
+pub(crate) struct FrameGuard;
+
+impl Drop for FrameGuard {
+    fn drop(&mut self) {
+        CONTEXT.with(|context| {
+            context.borrow_mut().pop();
+        });
+    }
+}
+
+pub fn scope<R>(attrs: impl IntoIterator<Item = Attribute>, f: impl FnOnce() -> R) -> R {
+    let _guard = enter(attrs.into_iter().collect());
+    f()
+}
+
+pub(crate) fn enter(frame: Vec<Attribute>) -> FrameGuard {
+    CONTEXT.with(|context| context.borrow_mut().push(frame));
+    FrameGuard
+}
+
+#[cfg(feature = "tokio")]
+pub async fn task_scope<F: std::future::Future>(
+    attrs: impl IntoIterator<Item = Attribute>,
+    future: F,
+) -> F::Output {
+    let mut frame = TASK_CONTEXT
+        .try_with(|context| context.clone())
+        .unwrap_or_default();
+    frame.extend(attrs);
+    TASK_CONTEXT.scope(frame, future).await
+}
+
+pub(crate) fn current() -> Vec<Attribute> {
+    #[allow(unused_mut)]
+    let mut attributes = Vec::new();
+    #[cfg(feature = "tokio")]
+    let _ = TASK_CONTEXT.try_with(|context| attributes.extend(context.iter().cloned()));
+    CONTEXT.with(|context| {
+        for frame in context.borrow().iter() {
+            attributes.extend(frame.iter().cloned());
+        }
+    });
+    attributes
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum VigilantError {
+    InvalidToken(String),
+    InvalidEndpoint(String),
+    InvalidDsn(String),
+    InvalidBatchConfig(String),
+    InvalidPattern(String),
+    InvalidInterval(String),
+    InvalidEnv(String),
+    AlreadyInitialized,
+}
+
+impl fmt::Display for VigilantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VigilantError::InvalidToken(reason) => write!(f, "invalid token: {}", reason),
+            VigilantError::InvalidEndpoint(reason) => write!(f, "invalid endpoint: {}", reason),
+            VigilantError::InvalidDsn(reason) => write!(f, "invalid DSN: {}", reason),
+            VigilantError::InvalidBatchConfig(reason) => {
+                write!(f, "invalid batch configuration: {}", reason)
+            }
+            VigilantError::InvalidPattern(reason) => {
+                write!(f, "invalid redaction pattern: {}", reason)
+            }
+            VigilantError::InvalidInterval(reason) => write!(f, "invalid interval: {}", reason),
+            VigilantError::InvalidEnv(reason) => {
+                write!(f, "invalid environment variable: {}", reason)
+            }
+            VigilantError::AlreadyInitialized => {
+                write!(f, "the global logger is already initialized")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VigilantError {}
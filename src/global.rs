@@ -0,0 +1,72 @@
+use crate::config::VigilantConfig;
+use crate::error::VigilantError;
+use crate::logger::Logger;
+use crate::logger_builder::LoggerBuilder;
+use crate::types::Attribute;
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+static GLOBAL: RwLock<Option<Logger>> = RwLock::new(None);
+static NOOP: OnceLock<Logger> = OnceLock::new();
+
+pub fn init(config: VigilantConfig) -> Result<(), VigilantError> {
+    init_with(LoggerBuilder::from_config(config))
+}
+
+pub fn init_with(builder: LoggerBuilder) -> Result<(), VigilantError> {
+    let mut global = GLOBAL.write().unwrap_or_else(|e| e.into_inner());
+    if global.is_some() {
+        return Err(VigilantError::AlreadyInitialized);
+    }
+    *global = Some(builder.try_build()?);
+    Ok(())
+}
+
+pub fn global() -> Logger {
+    let global = GLOBAL.read().unwrap_or_else(|e| e.into_inner());
+    match global.as_ref() {
+        Some(logger) => logger.clone(),
+        None => NOOP
+            .get_or_init(|| LoggerBuilder::new().noop(true).build())
+            .clone(),
+    }
+}
+
+pub fn shutdown() -> std::io::Result<()> {
+    let logger = GLOBAL.write().unwrap_or_else(|e| e.into_inner()).take();
+    let flushed = Logger::flush_all(SHUTDOWN_FLUSH_TIMEOUT);
+    if let Some(logger) = logger {
+        logger.shutdown()?;
+    }
+    flushed
+}
+
+pub fn debug(message: &str) {
+    global().debug(message);
+}
+
+pub fn info(message: &str) {
+    global().info(message);
+}
+
+pub fn warn(message: &str) {
+    global().warn(message);
+}
+
+pub fn error(message: &str) {
+    global().error(message);
+}
+
+pub fn fatal(message: &str) {
+    global().fatal(message);
+}
+
+pub fn add_breadcrumb(message: &str, data: impl IntoIterator<Item = Attribute>) {
+    global().add_breadcrumb(message, data);
+}
+
+pub fn capture_error<E: std::error::Error + ?Sized>(err: &E) {
+    global().capture_error(err);
+}
@@ -1,13 +1,71 @@
 mod adapters;
+mod attributes;
+mod breadcrumbs;
+mod capture;
+mod config;
+pub mod context;
+mod error;
+mod global;
 mod logger;
 mod logger_builder;
+mod macros;
+mod metrics;
+mod monitor;
+mod process_metrics;
+mod processor;
+mod rate_limit;
+mod redaction;
+mod sampling;
+#[cfg(all(unix, feature = "signals"))]
+mod signals;
+mod tail_buffer;
+mod template;
 mod types;
+mod util;
 
+pub use attributes::ToAttributes;
+pub use config::VigilantConfig;
+pub use error::VigilantError;
+pub use global::{
+    add_breadcrumb, capture_error, debug, error, fatal, global, info, init, init_with, shutdown,
+    warn,
+};
 pub use logger::Logger;
 pub use logger_builder::LoggerBuilder;
-pub use types::Attribute;
+#[doc(hidden)]
+pub use macros::__private;
+pub use macros::STATIC_MAX_LEVEL;
+pub use metrics::{Counter, Gauge, Histogram, Metrics};
+pub use monitor::{CheckIn, Heartbeat, Monitor};
+pub use processor::{Decision, Processor, ProcessorStage, StaticAttributes};
+pub use rate_limit::{RateLimiter, RateLimiterBuilder};
+pub use redaction::{Detector, RedactionMode, Redactor, RedactorBuilder};
+pub use sampling::{Sampler, SamplerBuilder, SamplingKey};
+pub use tail_buffer::{TailBuffer, TailBufferBuilder};
+pub use types::{Attribute, LogLevel, LogRecord};
 
 pub use adapters::adapter_env_logger::EnvLoggerAdapter;
+#[allow(deprecated)]
 pub use adapters::adapter_env_logger_builder::EnvLoggerAdapterBuilder;
 pub use adapters::adapter_tracing::TracingAdapter;
+#[allow(deprecated)]
 pub use adapters::adapter_tracing_builder::TracingAdapterBuilder;
+pub use adapters::adapter_tracing_builder::TracingLayerBuilder;
+
+#[cfg(feature = "derive")]
+pub use vigilant_derive::VigilantAttributes;
+
+#[cfg(feature = "reqwest-middleware")]
+pub use adapters::adapter_reqwest::ReqwestLogMiddleware;
+
+#[cfg(feature = "slog")]
+pub use adapters::adapter_slog::SlogDrain;
+#[cfg(feature = "slog")]
+#[allow(deprecated)]
+pub use adapters::adapter_slog_builder::SlogDrainBuilder;
+
+#[cfg(feature = "tower")]
+pub use adapters::adapter_tower::{HttpLogFuture, HttpLogLayer, HttpLogService};
+
+#[cfg(all(unix, feature = "signals"))]
+pub use signals::{SignalFlush, SignalFlushBuilder};
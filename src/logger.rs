@@ -1,23 +1,91 @@
 use chrono::Utc;
+use serde::Serialize;
+use std::backtrace::Backtrace;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    mpsc::{channel, Receiver, Sender},
-    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+    Arc, Mutex, Weak,
 };
 use std::thread;
-use std::time::Duration;
-
-use crate::types::{Attribute, LogLevel, LogMessage, MessageBatch, MessageType};
+use std::time::{Duration, Instant};
+
+use crate::breadcrumbs::{self, Breadcrumb};
+use crate::capture;
+use crate::context;
+use crate::logger_builder::LoggerBuilder;
+use crate::metrics::{MetricEvent, Metrics, MetricsAggregator};
+use crate::monitor::Monitor;
+use crate::process_metrics::ProcessCollector;
+use crate::processor::{Decision, Pipeline, Processor, ProcessorStage, StaticAttributes};
+use crate::rate_limit::RateLimiter;
+use crate::redaction::Redactor;
+use crate::tail_buffer::{current_scope_id, ScopeGuard, TailBuffer};
+use crate::template;
+use crate::types::{
+    Attribute, CheckInMessage, LogLevel, LogMessage, MessageBatch, MessageType, MetricMessage,
+};
 
 pub struct Logger {
     name: String,
     passthrough: bool,
     noop: bool,
+    level: LogLevel,
     inner: Arc<LoggerInner>,
 }
 
+pub(crate) struct WeakLogger {
+    name: String,
+    passthrough: bool,
+    noop: bool,
+    level: LogLevel,
+    inner: Weak<LoggerInner>,
+}
+
+impl WeakLogger {
+    pub(crate) fn upgrade(&self) -> Option<Logger> {
+        Some(Logger {
+            name: self.name.clone(),
+            passthrough: self.passthrough,
+            noop: self.noop,
+            level: self.level,
+            inner: self.inner.upgrade()?,
+        })
+    }
+}
+
+const PANIC_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+static REGISTRY: Mutex<Vec<Weak<LoggerInner>>> = Mutex::new(Vec::new());
+static NEXT_LOGGER_ID: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) enum WorkerMessage {
+    Log(LogMessage),
+    Metric(MetricEvent),
+    CheckIn(CheckInMessage),
+    Flush(Sender<()>),
+}
+
+struct BatcherSettings {
+    pipeline: Arc<Pipeline>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    passthrough: bool,
+    endpoint: String,
+    token: String,
+    max_batch_size: usize,
+    batch_interval: Duration,
+    metrics_interval: Duration,
+}
+
 struct LoggerInner {
-    tx: Sender<LogMessage>,
+    id: u64,
+    tx: Sender<WorkerMessage>,
+    pipeline: Arc<Pipeline>,
+    redactor: Option<Redactor>,
+    tail_buffer: Option<TailBuffer>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    breadcrumbs: usize,
+    in_app_crates: Vec<String>,
+    process_collector: Mutex<Option<ProcessCollector>>,
     stop_signal: Arc<AtomicBool>,
     worker_handle: Mutex<Option<thread::JoinHandle<()>>>,
 }
@@ -28,6 +96,7 @@ impl Clone for Logger {
             name: self.name.clone(),
             passthrough: self.passthrough,
             noop: self.noop,
+            level: self.level,
             inner: Arc::clone(&self.inner),
         }
     }
@@ -42,34 +111,132 @@ impl Logger {
         insecure: bool,
         noop: bool,
     ) -> Self {
-        let name = name.into();
-        let endpoint = endpoint.into();
-        let token = token.into();
+        LoggerBuilder::new()
+            .name(name)
+            .endpoint(endpoint)
+            .token(token)
+            .passthrough(passthrough)
+            .insecure(insecure)
+            .noop(noop)
+            .build()
+    }
 
-        let formatted_endpoint = if insecure {
-            format!("http://{}/api/message", endpoint)
-        } else {
-            format!("https://{}/api/message", endpoint)
+    pub(crate) fn from_builder(builder: LoggerBuilder) -> Self {
+        let LoggerBuilder {
+            config,
+            sampler,
+            redactor,
+            processors: user_processors,
+            processor_stage,
+            tail_buffer,
+            rate_limiter,
+            breadcrumbs,
+            in_app_crates,
+            process_metrics,
+            ..
+        } = builder;
+
+        let mut processors: Vec<Box<dyn Processor>> =
+            vec![Box::new(StaticAttributes::new(vec![Attribute::new(
+                "service.name",
+                config.name.clone(),
+            )]))];
+        if let Some(sampler) = sampler {
+            processors.push(Box::new(sampler));
+        }
+        processors.extend(user_processors);
+        if let Some(redactor) = &redactor {
+            processors.push(Box::new(redactor.clone()));
+        }
+        let pipeline = Arc::new(Pipeline::new(processor_stage, processors));
+        let rate_limiter = rate_limiter.map(Arc::new);
+
+        let settings = BatcherSettings {
+            pipeline: Arc::clone(&pipeline),
+            rate_limiter: rate_limiter.clone(),
+            passthrough: config.passthrough,
+            endpoint: config.formatted_endpoint(),
+            token: config.token.clone(),
+            max_batch_size: config.max_batch_size.max(1),
+            batch_interval: Duration::from_millis(config.batch_interval_ms.max(1)),
+            metrics_interval: Duration::from_millis(config.metrics_interval_ms.max(1)),
         };
 
-        let (tx, rx) = channel::<LogMessage>();
+        let (tx, rx) = channel::<WorkerMessage>();
         let stop_signal = Arc::new(AtomicBool::new(false));
         let stop_signal_cloned = Arc::clone(&stop_signal);
 
-        let worker_handle = thread::spawn(move || {
-            Self::run_batcher(rx, formatted_endpoint, token, stop_signal_cloned);
+        let worker_handle = if config.noop {
+            None
+        } else {
+            Some(thread::spawn(move || {
+                Self::run_batcher(rx, settings, stop_signal_cloned);
+            }))
+        };
+
+        let inner = Arc::new(LoggerInner {
+            id: NEXT_LOGGER_ID.fetch_add(1, Ordering::Relaxed),
+            tx,
+            pipeline,
+            redactor,
+            tail_buffer,
+            rate_limiter,
+            breadcrumbs,
+            in_app_crates,
+            process_collector: Mutex::new(None),
+            stop_signal,
+            worker_handle: Mutex::new(worker_handle),
         });
 
-        Logger {
-            name,
-            passthrough,
-            noop,
-            inner: Arc::new(LoggerInner {
-                tx,
-                stop_signal,
-                worker_handle: Mutex::new(Some(worker_handle)),
-            }),
+        if !config.noop {
+            if let Ok(mut registry) = REGISTRY.lock() {
+                registry.retain(|logger| logger.strong_count() > 0);
+                registry.push(Arc::downgrade(&inner));
+            }
         }
+
+        let logger = Logger {
+            name: config.name,
+            passthrough: config.passthrough,
+            noop: config.noop,
+            level: config.level,
+            inner,
+        };
+
+        if let Some(interval) = process_metrics.filter(|_| !logger.noop) {
+            let collector = ProcessCollector::spawn(logger.downgrade(), interval);
+            if let Ok(mut process_collector) = logger.inner.process_collector.lock() {
+                *process_collector = Some(collector);
+            }
+        }
+
+        logger
+    }
+
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    pub(crate) fn downgrade(&self) -> WeakLogger {
+        WeakLogger {
+            name: self.name.clone(),
+            passthrough: self.passthrough,
+            noop: self.noop,
+            level: self.level,
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        !self.noop && level.severity() >= self.level.severity()
+    }
+
+    pub fn metrics(&self) -> Metrics {
+        Metrics::new(self.clone())
+    }
+
+    pub fn monitor<S: Into<String>>(&self, slug: S) -> Monitor {
+        Monitor::new(self.clone(), slug)
     }
 
     pub fn debug(&self, message: &str) {
@@ -88,6 +255,10 @@ impl Logger {
         self.log(LogLevel::ERROR, message, None, Vec::new());
     }
 
+    pub fn fatal(&self, message: &str) {
+        self.log(LogLevel::FATAL, message, None, Vec::new());
+    }
+
     pub fn debug_with_attrs(&self, message: &str, attrs: impl IntoIterator<Item = Attribute>) {
         self.log(LogLevel::DEBUG, message, None, attrs);
     }
@@ -104,7 +275,183 @@ impl Logger {
         self.log(LogLevel::ERROR, message, None, attrs);
     }
 
+    pub fn fatal_with_attrs(&self, message: &str, attrs: impl IntoIterator<Item = Attribute>) {
+        self.log(LogLevel::FATAL, message, None, attrs);
+    }
+
+    pub fn debug_with_value<T: Serialize + ?Sized>(&self, message: &str, key: &str, value: &T) {
+        if self.enabled(LogLevel::DEBUG) {
+            self.log(
+                LogLevel::DEBUG,
+                message,
+                None,
+                Attribute::from_serialize(key, value),
+            );
+        }
+    }
+
+    pub fn warn_with_value<T: Serialize + ?Sized>(&self, message: &str, key: &str, value: &T) {
+        if self.enabled(LogLevel::WARNING) {
+            self.log(
+                LogLevel::WARNING,
+                message,
+                None,
+                Attribute::from_serialize(key, value),
+            );
+        }
+    }
+
+    pub fn info_with_value<T: Serialize + ?Sized>(&self, message: &str, key: &str, value: &T) {
+        if self.enabled(LogLevel::INFO) {
+            self.log(
+                LogLevel::INFO,
+                message,
+                None,
+                Attribute::from_serialize(key, value),
+            );
+        }
+    }
+
+    pub fn error_with_value<T: Serialize + ?Sized>(&self, message: &str, key: &str, value: &T) {
+        if self.enabled(LogLevel::ERROR) {
+            self.log(
+                LogLevel::ERROR,
+                message,
+                None,
+                Attribute::from_serialize(key, value),
+            );
+        }
+    }
+
+    pub fn fatal_with_value<T: Serialize + ?Sized>(&self, message: &str, key: &str, value: &T) {
+        if self.enabled(LogLevel::FATAL) {
+            self.log(
+                LogLevel::FATAL,
+                message,
+                None,
+                Attribute::from_serialize(key, value),
+            );
+        }
+    }
+
+    pub fn debug_tmpl(&self, template: &str, attrs: impl IntoIterator<Item = Attribute>) {
+        self.log_template(LogLevel::DEBUG, template, attrs);
+    }
+
+    pub fn warn_tmpl(&self, template: &str, attrs: impl IntoIterator<Item = Attribute>) {
+        self.log_template(LogLevel::WARNING, template, attrs);
+    }
+
+    pub fn info_tmpl(&self, template: &str, attrs: impl IntoIterator<Item = Attribute>) {
+        self.log_template(LogLevel::INFO, template, attrs);
+    }
+
+    pub fn error_tmpl(&self, template: &str, attrs: impl IntoIterator<Item = Attribute>) {
+        self.log_template(LogLevel::ERROR, template, attrs);
+    }
+
+    pub fn fatal_tmpl(&self, template: &str, attrs: impl IntoIterator<Item = Attribute>) {
+        self.log_template(LogLevel::FATAL, template, attrs);
+    }
+
+    pub fn add_breadcrumb(&self, message: &str, data: impl IntoIterator<Item = Attribute>) {
+        if self.noop || self.inner.breadcrumbs == 0 {
+            return;
+        }
+        let mut breadcrumb =
+            Breadcrumb::new(current_timestamp_rfc3339(), LogLevel::INFO, message, data);
+        if let Some(redactor) = &self.inner.redactor {
+            breadcrumb.redact(redactor);
+        }
+        breadcrumbs::record(self.inner.id, self.inner.breadcrumbs, breadcrumb);
+    }
+
+    pub fn capture_error<E: std::error::Error + ?Sized>(&self, err: &E) {
+        if self.enabled(LogLevel::ERROR) {
+            let attrs = capture::error_attributes(err, &self.inner.in_app_crates);
+            self.log(LogLevel::ERROR, &err.to_string(), None, attrs);
+        }
+    }
+
+    pub fn flush(&self, timeout: Duration) -> std::io::Result<()> {
+        if self.noop {
+            return Ok(());
+        }
+
+        self.inner.flush(Instant::now().checked_add(timeout))
+    }
+
+    pub fn flush_all(timeout: Duration) -> std::io::Result<()> {
+        let deadline = Instant::now().checked_add(timeout);
+        let loggers: Vec<Arc<LoggerInner>> = match REGISTRY.lock() {
+            Ok(registry) => registry.iter().filter_map(Weak::upgrade).collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let mut result = Ok(());
+        for logger in loggers {
+            if let Err(e) = logger.flush(deadline) {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    pub fn install_panic_hook(&self) {
+        let logger = self.clone();
+        let previous_hook = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "<non-string panic payload>".to_string()
+            };
+
+            let mut attributes = vec![
+                Attribute::new(
+                    "thread.name",
+                    thread::current().name().unwrap_or("<unnamed>"),
+                ),
+                Attribute::new("backtrace", Backtrace::force_capture().to_string()),
+            ];
+            if let Some(location) = info.location() {
+                attributes.push(Attribute::new("file", location.file()));
+                attributes.push(Attribute::new("line", location.line().to_string()));
+                attributes.push(Attribute::new("column", location.column().to_string()));
+            }
+
+            logger.fatal_with_attrs(&format!("panic: {}", message), attributes);
+            let _ = logger.flush(PANIC_FLUSH_TIMEOUT);
+
+            previous_hook(info);
+        }));
+    }
+
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        let guard = ScopeGuard::enter();
+        let result = f();
+        self.discard_buffered(&guard.id);
+        result
+    }
+
+    pub fn discard_buffered(&self, key: &str) {
+        if let Some(tail_buffer) = &self.inner.tail_buffer {
+            tail_buffer.discard(key);
+        }
+    }
+
     pub fn shutdown(&self) -> std::io::Result<()> {
+        let collector = match self.inner.process_collector.lock() {
+            Ok(mut collector) => collector.take(),
+            Err(_) => None,
+        };
+        if let Some(collector) = collector {
+            collector.stop();
+        }
         self.inner.stop_signal.store(true, Ordering::SeqCst);
         if let Ok(mut handle) = self.inner.worker_handle.lock() {
             if let Some(h) = handle.take() {
@@ -114,6 +461,26 @@ impl Logger {
         Ok(())
     }
 
+    pub(crate) fn log_template(
+        &self,
+        level: LogLevel,
+        template: &str,
+        attrs: impl IntoIterator<Item = Attribute>,
+    ) {
+        if !self.enabled(level) {
+            return;
+        }
+
+        let mut attrs: Vec<Attribute> = attrs.into_iter().collect();
+        let message = template::render(template, &attrs);
+        attrs.push(Attribute::new("message.template", template));
+        attrs.push(Attribute::new(
+            "message.template.hash",
+            template::template_hash(template),
+        ));
+        self.log(level, &message, None, attrs);
+    }
+
     fn log(
         &self,
         level: LogLevel,
@@ -121,13 +488,18 @@ impl Logger {
         err: Option<&dyn std::error::Error>,
         attrs: impl IntoIterator<Item = Attribute>,
     ) {
-        if self.noop {
+        if !self.enabled(level) {
             return;
         }
 
+        let timestamp = current_timestamp_rfc3339();
         let mut map = std::collections::HashMap::new();
-        map.insert("service.name".to_string(), self.name.clone());
-
+        if let Some(scope_id) = current_scope_id() {
+            map.insert("scope.id".to_string(), scope_id);
+        }
+        for attr in context::current() {
+            map.insert(attr.key, attr.value);
+        }
         for attr in attrs {
             map.insert(attr.key, attr.value);
         }
@@ -136,50 +508,148 @@ impl Logger {
             map.insert("error".to_string(), e.to_string());
         }
 
-        let log_message = LogMessage {
-            timestamp: current_timestamp_rfc3339(),
+        if self.inner.breadcrumbs > 0 && level.severity() >= LogLevel::ERROR.severity() {
+            if let Some(trail) = breadcrumbs::to_json(self.inner.id) {
+                map.insert("breadcrumbs".to_string(), trail);
+            }
+        }
+
+        let mut log_message = LogMessage {
+            timestamp,
             body: message.to_string(),
             level,
             attributes: map,
         };
 
-        if let Err(_e) = self.inner.tx.send(log_message) {}
+        if self.inner.pipeline.stage == ProcessorStage::CallingThread {
+            if self.inner.pipeline.run(&mut log_message) == Decision::Drop {
+                return;
+            }
+            if let Some(rate_limiter) = &self.inner.rate_limiter {
+                if !rate_limiter.allow(&log_message) {
+                    return;
+                }
+            }
+            if self.passthrough {
+                print_passthrough(&log_message);
+            }
+        }
 
-        self.log_passthrough(level, message, err);
+        self.record_breadcrumb(&log_message);
+
+        match &self.inner.tail_buffer {
+            Some(tail_buffer) => {
+                for message in tail_buffer.handle(log_message) {
+                    if let Err(_e) = self.inner.tx.send(WorkerMessage::Log(message)) {}
+                }
+            }
+            None => if let Err(_e) = self.inner.tx.send(WorkerMessage::Log(log_message)) {},
+        }
     }
 
-    fn log_passthrough(&self, level: LogLevel, message: &str, err: Option<&dyn std::error::Error>) {
-        if !self.passthrough {
+    fn record_breadcrumb(&self, record: &LogMessage) {
+        if self.inner.breadcrumbs == 0 || record.level.severity() >= LogLevel::ERROR.severity() {
             return;
         }
 
-        if let Some(e) = err {
-            println!("[{:?}] {} error=\"{}\"", level, message, e);
-        } else {
-            println!("[{:?}] {}", level, message);
+        let mut breadcrumb = Breadcrumb::new(
+            record.timestamp.clone(),
+            record.level,
+            &record.body,
+            Vec::new(),
+        );
+        if self.inner.pipeline.stage == ProcessorStage::Worker {
+            if let Some(redactor) = &self.inner.redactor {
+                breadcrumb.redact(redactor);
+            }
         }
+        breadcrumbs::record(self.inner.id, self.inner.breadcrumbs, breadcrumb);
+    }
+
+    pub(crate) fn record_metric(&self, mut event: MetricEvent) {
+        if self.noop {
+            return;
+        }
+
+        if let Some(redactor) = &self.inner.redactor {
+            redactor.redact_attributes(event.attributes.iter_mut());
+        }
+        event
+            .attributes
+            .insert("service.name".to_string(), self.name.clone());
+
+        if let Err(_e) = self.inner.tx.send(WorkerMessage::Metric(event)) {}
+    }
+
+    pub(crate) fn record_check_in(&self, mut check_in: CheckInMessage) {
+        if self.noop {
+            return;
+        }
+
+        if let Some(redactor) = &self.inner.redactor {
+            check_in.error = check_in.error.map(|error| redactor.redact_value(&error));
+            redactor.redact_attributes(check_in.attributes.iter_mut());
+        }
+        check_in
+            .attributes
+            .insert("service.name".to_string(), self.name.clone());
+
+        if let Err(_e) = self.inner.tx.send(WorkerMessage::CheckIn(check_in)) {}
     }
 
     fn run_batcher(
-        rx: Receiver<LogMessage>,
-        endpoint: String,
-        token: String,
+        rx: Receiver<WorkerMessage>,
+        settings: BatcherSettings,
         stop_signal: Arc<AtomicBool>,
     ) {
-        let max_batch_size = 100;
-        let batch_interval = Duration::from_millis(100);
+        let BatcherSettings {
+            pipeline,
+            rate_limiter,
+            passthrough,
+            endpoint,
+            token,
+            max_batch_size,
+            batch_interval,
+            metrics_interval,
+        } = settings;
         let mut buffer = Vec::with_capacity(max_batch_size);
+        let mut aggregator = MetricsAggregator::new();
+        let mut last_metrics_flush = Instant::now();
         let client = reqwest::blocking::Client::new();
 
         loop {
             match rx.recv_timeout(batch_interval) {
-                Ok(msg) => {
-                    buffer.push(msg);
-                    if buffer.len() >= max_batch_size {
-                        Self::send_batch(&client, &endpoint, &token, &mut buffer);
+                Ok(WorkerMessage::Log(mut msg)) => {
+                    let keep = pipeline.stage == ProcessorStage::CallingThread
+                        || (pipeline.run(&mut msg) == Decision::Keep
+                            && rate_limiter.as_ref().is_none_or(|l| l.allow(&msg)));
+                    if keep {
+                        if passthrough && pipeline.stage == ProcessorStage::Worker {
+                            print_passthrough(&msg);
+                        }
+                        buffer.push(msg);
+                        if buffer.len() >= max_batch_size {
+                            Self::send_batch(&client, &endpoint, &token, &mut buffer);
+                        }
                     }
                 }
-                Err(_timeout_or_disconnect) => {
+                Ok(WorkerMessage::Metric(event)) => {
+                    aggregator.record(event);
+                }
+                Ok(WorkerMessage::CheckIn(check_in)) => {
+                    Self::send_check_in(&client, &endpoint, &token, check_in);
+                }
+                Ok(WorkerMessage::Flush(ack)) => {
+                    if let Some(rate_limiter) = &rate_limiter {
+                        buffer.extend(rate_limiter.take_summaries(true));
+                    }
+                    Self::send_batch(&client, &endpoint, &token, &mut buffer);
+                    Self::send_metrics(&client, &endpoint, &token, aggregator.drain());
+                    last_metrics_flush = Instant::now();
+                    let _ = ack.send(());
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+                Err(RecvTimeoutError::Timeout) => {
                     if !buffer.is_empty() {
                         Self::send_batch(&client, &endpoint, &token, &mut buffer);
                     }
@@ -188,11 +658,29 @@ impl Logger {
                     }
                 }
             }
+
+            if let Some(rate_limiter) = &rate_limiter {
+                for summary in rate_limiter.take_summaries(false) {
+                    if passthrough {
+                        print_passthrough(&summary);
+                    }
+                    buffer.push(summary);
+                }
+            }
+
+            if last_metrics_flush.elapsed() >= metrics_interval {
+                Self::send_metrics(&client, &endpoint, &token, aggregator.drain());
+                last_metrics_flush = Instant::now();
+            }
         }
 
+        if let Some(rate_limiter) = &rate_limiter {
+            buffer.extend(rate_limiter.take_summaries(true));
+        }
         if !buffer.is_empty() {
             Self::send_batch(&client, &endpoint, &token, &mut buffer);
         }
+        Self::send_metrics(&client, &endpoint, &token, aggregator.drain());
     }
 
     fn send_batch(
@@ -207,20 +695,95 @@ impl Logger {
         let current_batch = MessageBatch {
             token: token.to_string(),
             msg_type: MessageType::Logs,
-            logs: buffer.drain(..).collect(),
+            logs: std::mem::take(buffer),
+            metrics: Vec::new(),
+            check_ins: Vec::new(),
+        };
+
+        Self::post_batch(client, endpoint, &current_batch);
+    }
+
+    fn send_metrics(
+        client: &reqwest::blocking::Client,
+        endpoint: &str,
+        token: &str,
+        metrics: Vec<MetricMessage>,
+    ) {
+        if metrics.is_empty() {
+            return;
+        }
+        let current_batch = MessageBatch {
+            token: token.to_string(),
+            msg_type: MessageType::Metrics,
+            logs: Vec::new(),
+            metrics,
+            check_ins: Vec::new(),
         };
 
+        Self::post_batch(client, endpoint, &current_batch);
+    }
+
+    fn send_check_in(
+        client: &reqwest::blocking::Client,
+        endpoint: &str,
+        token: &str,
+        check_in: CheckInMessage,
+    ) {
+        let current_batch = MessageBatch {
+            token: token.to_string(),
+            msg_type: MessageType::CheckIns,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+            check_ins: vec![check_in],
+        };
+
+        Self::post_batch(client, endpoint, &current_batch);
+    }
+
+    fn post_batch(client: &reqwest::blocking::Client, endpoint: &str, batch: &MessageBatch) {
         if let Err(e) = client
             .post(endpoint)
-            .json(&current_batch)
+            .json(batch)
             .header("Content-Type", "application/json")
             .send()
         {
-            eprintln!("Failed to send log batch: {}", e);
+            eprintln!("Failed to send {:?} batch: {}", batch.msg_type, e);
+        }
+    }
+}
+
+impl LoggerInner {
+    fn flush(&self, deadline: Option<Instant>) -> std::io::Result<()> {
+        let (ack_tx, ack_rx) = channel();
+        if self.tx.send(WorkerMessage::Flush(ack_tx)).is_err() {
+            return Ok(());
+        }
+
+        let acked = match deadline {
+            Some(deadline) => ack_rx
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .is_ok(),
+            None => ack_rx.recv().is_ok(),
+        };
+        if acked {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "timed out waiting for the log batch to flush",
+            ))
         }
     }
 }
 
-fn current_timestamp_rfc3339() -> String {
+fn print_passthrough(message: &LogMessage) {
+    if let Some(e) = message.attributes.get("error") {
+        println!("[{:?}] {} error=\"{}\"", message.level, message.body, e);
+    } else {
+        println!("[{:?}] {}", message.level, message.body);
+    }
+}
+
+pub(crate) fn current_timestamp_rfc3339() -> String {
     Utc::now().to_rfc3339()
 }
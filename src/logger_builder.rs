@@ -1,64 +1,249 @@
+use crate::config::{ConfigOverrides, VigilantConfig};
+use crate::error::VigilantError;
 use crate::logger::Logger;
+use crate::metrics::Metrics;
+use crate::processor::{Processor, ProcessorStage, StaticAttributes};
+use crate::rate_limit::RateLimiter;
+use crate::redaction::Redactor;
+use crate::sampling::Sampler;
+use crate::tail_buffer::TailBuffer;
+use crate::types::{Attribute, LogLevel};
+#[cfg(feature = "tower")]
+use crate::HttpLogLayer;
+#[cfg(feature = "reqwest-middleware")]
+use crate::ReqwestLogMiddleware;
+#[cfg(feature = "slog")]
+use crate::SlogDrain;
+use crate::{EnvLoggerAdapter, TracingAdapter};
+use std::time::Duration;
 
-pub struct LoggerBuilder<'a> {
-    name: &'a str,
-    endpoint: &'a str,
-    token: &'a str,
-    passthrough: bool,
-    insecure: bool,
-    noop: bool,
+pub struct LoggerBuilder {
+    pub(crate) config: VigilantConfig,
+    pub(crate) sampler: Option<Sampler>,
+    pub(crate) redactor: Option<Redactor>,
+    pub(crate) processors: Vec<Box<dyn Processor>>,
+    pub(crate) tail_buffer: Option<TailBuffer>,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) breadcrumbs: usize,
+    pub(crate) in_app_crates: Vec<String>,
+    pub(crate) process_metrics: Option<Duration>,
+    pub(crate) processor_stage: ProcessorStage,
+    dsn_error: Option<VigilantError>,
+    env_errors: Vec<VigilantError>,
 }
 
-impl<'a> LoggerBuilder<'a> {
-    pub fn new() -> Self {
+impl Default for LoggerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoggerBuilder {
+    pub fn from_env() -> Self {
+        let mut overrides = ConfigOverrides::from_env();
+        let env_errors = std::mem::take(&mut overrides.errors);
+        let mut config = VigilantConfig::default();
+        config.apply(overrides);
+        Self {
+            env_errors,
+            ..Self::from_config(config)
+        }
+    }
+
+    pub fn from_config(config: VigilantConfig) -> Self {
         Self {
-            name: "sample-app",
-            endpoint: "ingress.vigilant.run",
-            token: "tk_1234567890",
-            passthrough: false,
-            insecure: false,
-            noop: false,
+            config,
+            sampler: None,
+            redactor: None,
+            processors: Vec::new(),
+            tail_buffer: None,
+            rate_limiter: None,
+            breadcrumbs: 0,
+            in_app_crates: Vec::new(),
+            process_metrics: None,
+            processor_stage: ProcessorStage::CallingThread,
+            dsn_error: None,
+            env_errors: Vec::new(),
         }
     }
 
-    pub fn name(mut self, name: &'a str) -> Self {
-        self.name = name;
+    pub fn new() -> Self {
+        Self::from_config(VigilantConfig::default())
+    }
+
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.config.name = name.into();
         self
     }
 
-    pub fn endpoint(mut self, endpoint: &'a str) -> Self {
-        self.endpoint = endpoint;
+    pub fn endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.config.endpoint = endpoint.into();
         self
     }
 
-    pub fn token(mut self, token: &'a str) -> Self {
-        self.token = token;
+    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+        self.config.token = token.into();
+        self
+    }
+
+    pub fn dsn(mut self, dsn: &str) -> Self {
+        match ConfigOverrides::from_dsn(dsn) {
+            Ok(overrides) => self.config.apply(overrides),
+            Err(e) => self.dsn_error = Some(e),
+        }
         self
     }
 
     pub fn passthrough(mut self, enabled: bool) -> Self {
-        self.passthrough = enabled;
+        self.config.passthrough = enabled;
         self
     }
 
     pub fn insecure(mut self, enabled: bool) -> Self {
-        self.insecure = enabled;
+        self.config.insecure = enabled;
         self
     }
 
     pub fn noop(mut self, enabled: bool) -> Self {
-        self.noop = enabled;
+        self.config.noop = enabled;
+        self
+    }
+
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.config.level = level;
+        self
+    }
+
+    pub fn max_batch_size(mut self, size: usize) -> Self {
+        self.config.max_batch_size = size;
+        self
+    }
+
+    pub fn batch_interval(mut self, interval: Duration) -> Self {
+        self.config.batch_interval_ms = interval.as_millis() as u64;
+        self
+    }
+
+    pub fn metrics_interval(mut self, interval: Duration) -> Self {
+        self.config.metrics_interval_ms = interval.as_millis() as u64;
+        self
+    }
+
+    pub fn sampler(mut self, sampler: Sampler) -> Self {
+        self.sampler = Some(sampler);
         self
     }
 
-    pub fn build(self) -> Logger {
-        Logger::new(
-            self.name,
-            self.endpoint,
-            self.token,
-            self.passthrough,
-            self.insecure,
-            self.noop,
-        )
+    pub fn redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    pub fn processor<P: Processor + 'static>(mut self, processor: P) -> Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    pub fn processor_stage(mut self, stage: ProcessorStage) -> Self {
+        self.processor_stage = stage;
+        self
+    }
+
+    pub fn tail_buffer(mut self, tail_buffer: TailBuffer) -> Self {
+        self.tail_buffer = Some(tail_buffer);
+        self
+    }
+
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn breadcrumbs(mut self, capacity: usize) -> Self {
+        self.breadcrumbs = capacity;
+        self
+    }
+
+    pub fn in_app_crates<I, S>(mut self, crates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.in_app_crates
+            .extend(crates.into_iter().map(|name| name.into().replace('-', "_")));
+        self
+    }
+
+    pub fn process_metrics(mut self, interval: Duration) -> Self {
+        self.process_metrics = Some(interval);
+        self
+    }
+
+    pub fn attributes(self, attrs: impl IntoIterator<Item = Attribute>) -> Self {
+        self.processor(StaticAttributes::new(attrs))
+    }
+
+    pub fn build(mut self) -> Logger {
+        for e in &self.env_errors {
+            eprintln!("Ignoring {}", e);
+        }
+        if let Some(e) = &self.dsn_error {
+            eprintln!("Ignoring DSN: {}", e);
+        }
+        if let Some(e) = self.process_metrics_error() {
+            eprintln!("Ignoring process metrics: {}", e);
+            self.process_metrics = None;
+        }
+        Logger::from_builder(self)
+    }
+
+    pub fn try_build(mut self) -> Result<Logger, VigilantError> {
+        if !self.env_errors.is_empty() {
+            return Err(self.env_errors.remove(0));
+        }
+        if let Some(e) = self.dsn_error.take() {
+            return Err(e);
+        }
+        if let Some(e) = self.process_metrics_error() {
+            return Err(e);
+        }
+        self.config.validate()?;
+        Ok(Logger::from_builder(self))
+    }
+
+    fn process_metrics_error(&self) -> Option<VigilantError> {
+        match self.process_metrics {
+            Some(interval) if interval.is_zero() => Some(VigilantError::InvalidInterval(
+                "process_metrics interval must be greater than zero".to_string(),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn build_metrics(self) -> Metrics {
+        self.build().metrics()
+    }
+
+    pub fn tracing_layer(self) -> TracingAdapter {
+        TracingAdapter::from_logger(self.build())
+    }
+
+    pub fn log_adapter(self) -> EnvLoggerAdapter {
+        EnvLoggerAdapter::from_logger(self.build())
+    }
+
+    #[cfg(feature = "tower")]
+    pub fn http_layer(self) -> HttpLogLayer {
+        HttpLogLayer::from_logger(self.build())
+    }
+
+    #[cfg(feature = "reqwest-middleware")]
+    pub fn reqwest_middleware(self) -> ReqwestLogMiddleware {
+        ReqwestLogMiddleware::from_logger(self.build())
+    }
+
+    #[cfg(feature = "slog")]
+    pub fn slog_drain(self) -> SlogDrain {
+        SlogDrain::from_logger(self.build())
     }
 }
@@ -0,0 +1,185 @@
+use crate::logger::Logger;
+use crate::types::{Attribute, LogLevel};
+
+pub const STATIC_MAX_LEVEL: Option<LogLevel> = if cfg!(feature = "max_level_off") {
+    None
+} else if cfg!(feature = "max_level_error") {
+    Some(LogLevel::ERROR)
+} else if cfg!(feature = "max_level_warn") {
+    Some(LogLevel::WARNING)
+} else if cfg!(feature = "max_level_info") {
+    Some(LogLevel::INFO)
+} else {
+    Some(LogLevel::DEBUG)
+};
+
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+    use std::fmt::{Debug, Display};
+
+    pub use crate::attributes::join_key;
+    pub use crate::redaction::MASK as REDACTED;
+
+    #[inline]
+    pub const fn static_enabled(level: LogLevel) -> bool {
+        match STATIC_MAX_LEVEL {
+            Some(max) => level.severity() >= max.severity(),
+            None => false,
+        }
+    }
+
+    pub fn display<V: Display + ?Sized>(value: &V) -> String {
+        value.to_string()
+    }
+
+    pub fn debug<V: Debug + ?Sized>(value: &V) -> String {
+        format!("{:?}", value)
+    }
+
+    pub fn log(
+        logger: &Logger,
+        level: LogLevel,
+        template: &str,
+        callsite: (&'static str, u32, &'static str),
+        attrs: Vec<Attribute>,
+    ) {
+        let (file, line, module_path) = callsite;
+        let mut attributes = Vec::with_capacity(attrs.len() + 3);
+        attributes.push(Attribute::new("file", file));
+        attributes.push(Attribute::new("line", line.to_string()));
+        attributes.push(Attribute::new("module_path", module_path));
+        attributes.extend(attrs);
+        logger.log_template(level, template, attributes);
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __vigilant_attrs {
+    ($attrs:ident) => {};
+    ($attrs:ident $($key:ident).+ = %$value:expr $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new(
+            stringify!($($key).+),
+            $crate::__private::display(&$value),
+        ));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident $($key:ident).+ = ?$value:expr $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new(
+            stringify!($($key).+),
+            $crate::__private::debug(&$value),
+        ));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident $($key:ident).+ = $value:expr $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new(
+            stringify!($($key).+),
+            $crate::__private::display(&$value),
+        ));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident $key:literal = %$value:expr $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new($key, $crate::__private::display(&$value)));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident $key:literal = ?$value:expr $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new($key, $crate::__private::debug(&$value)));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident $key:literal = $value:expr $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new($key, $crate::__private::display(&$value)));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident %$($key:ident).+ $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new(
+            stringify!($($key).+),
+            $crate::__private::display(&$($key).+),
+        ));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident ?$($key:ident).+ $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new(
+            stringify!($($key).+),
+            $crate::__private::debug(&$($key).+),
+        ));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+    ($attrs:ident $($key:ident).+ $(, $($rest:tt)*)?) => {
+        $attrs.push($crate::Attribute::new(
+            stringify!($($key).+),
+            $crate::__private::display(&$($key).+),
+        ));
+        $( $crate::__vigilant_attrs!($attrs $($rest)*); )?
+    };
+}
+
+#[macro_export]
+macro_rules! log {
+    ($logger:expr, $level:expr, $template:literal $(, $($fields:tt)*)?) => {{
+        let level: $crate::LogLevel = $level;
+        let logger: &$crate::Logger = &$logger;
+        if $crate::__private::static_enabled(level) && logger.enabled(level) {
+            #[allow(unused_mut)]
+            let mut attrs = ::std::vec::Vec::new();
+            $( $crate::__vigilant_attrs!(attrs $($fields)*); )?
+            $crate::__private::log(
+                logger,
+                level,
+                $template,
+                (file!(), line!(), module_path!()),
+                attrs,
+            );
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($template:literal $($rest:tt)*) => {
+        $crate::log!($crate::global(), $crate::LogLevel::DEBUG, $template $($rest)*)
+    };
+    ($logger:expr, $($rest:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::DEBUG, $($rest)+)
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($template:literal $($rest:tt)*) => {
+        $crate::log!($crate::global(), $crate::LogLevel::INFO, $template $($rest)*)
+    };
+    ($logger:expr, $($rest:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::INFO, $($rest)+)
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($template:literal $($rest:tt)*) => {
+        $crate::log!($crate::global(), $crate::LogLevel::WARNING, $template $($rest)*)
+    };
+    ($logger:expr, $($rest:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::WARNING, $($rest)+)
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($template:literal $($rest:tt)*) => {
+        $crate::log!($crate::global(), $crate::LogLevel::ERROR, $template $($rest)*)
+    };
+    ($logger:expr, $($rest:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::ERROR, $($rest)+)
+    };
+}
+
+#[macro_export]
+macro_rules! fatal {
+    ($template:literal $($rest:tt)*) => {
+        $crate::log!($crate::global(), $crate::LogLevel::FATAL, $template $($rest)*)
+    };
+    ($logger:expr, $($rest:tt)+) => {
+        $crate::log!($logger, $crate::LogLevel::FATAL, $($rest)+)
+    };
+}
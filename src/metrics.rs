@@ -0,0 +1,185 @@
+use crate::logger::{current_timestamp_rfc3339, Logger};
+use crate::types::{Attribute, MetricMessage, MetricType};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone)]
+pub struct Metrics {
+    logger: Logger,
+}
+
+impl Metrics {
+    pub(crate) fn new(logger: Logger) -> Self {
+        Self { logger }
+    }
+
+    pub fn counter<S: Into<String>>(&self, name: S) -> Counter {
+        Counter(Instrument::new(
+            self.logger.clone(),
+            name,
+            MetricType::Counter,
+        ))
+    }
+
+    pub fn gauge<S: Into<String>>(&self, name: S) -> Gauge {
+        Gauge(Instrument::new(
+            self.logger.clone(),
+            name,
+            MetricType::Gauge,
+        ))
+    }
+
+    pub fn histogram<S: Into<String>>(&self, name: S) -> Histogram {
+        Histogram(Instrument::new(
+            self.logger.clone(),
+            name,
+            MetricType::Histogram,
+        ))
+    }
+
+    pub fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    pub fn shutdown(&self) -> std::io::Result<()> {
+        self.logger.shutdown()
+    }
+}
+
+#[derive(Clone)]
+struct Instrument {
+    logger: Logger,
+    name: String,
+    metric_type: MetricType,
+}
+
+impl Instrument {
+    fn new<S: Into<String>>(logger: Logger, name: S, metric_type: MetricType) -> Self {
+        Self {
+            logger,
+            name: name.into(),
+            metric_type,
+        }
+    }
+
+    fn record(&self, value: f64, attrs: impl IntoIterator<Item = Attribute>) {
+        self.logger.record_metric(MetricEvent {
+            name: self.name.clone(),
+            metric_type: self.metric_type,
+            value,
+            attributes: attrs.into_iter().map(|a| (a.key, a.value)).collect(),
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct Counter(Instrument);
+
+impl Counter {
+    pub fn increment(&self) {
+        self.0.record(1.0, Vec::new());
+    }
+
+    pub fn add(&self, value: f64) {
+        self.0.record(value, Vec::new());
+    }
+
+    pub fn add_with_attrs(&self, value: f64, attrs: impl IntoIterator<Item = Attribute>) {
+        self.0.record(value, attrs);
+    }
+}
+
+#[derive(Clone)]
+pub struct Gauge(Instrument);
+
+impl Gauge {
+    pub fn set(&self, value: f64) {
+        self.0.record(value, Vec::new());
+    }
+
+    pub fn set_with_attrs(&self, value: f64, attrs: impl IntoIterator<Item = Attribute>) {
+        self.0.record(value, attrs);
+    }
+}
+
+#[derive(Clone)]
+pub struct Histogram(Instrument);
+
+impl Histogram {
+    pub fn record(&self, value: f64) {
+        self.0.record(value, Vec::new());
+    }
+
+    pub fn record_with_attrs(&self, value: f64, attrs: impl IntoIterator<Item = Attribute>) {
+        self.0.record(value, attrs);
+    }
+}
+
+pub(crate) struct MetricEvent {
+    pub(crate) name: String,
+    pub(crate) metric_type: MetricType,
+    pub(crate) value: f64,
+    pub(crate) attributes: BTreeMap<String, String>,
+}
+
+type MetricKey = (String, MetricType, BTreeMap<String, String>);
+
+struct Aggregate {
+    value: f64,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+pub(crate) struct MetricsAggregator {
+    aggregates: HashMap<MetricKey, Aggregate>,
+}
+
+impl MetricsAggregator {
+    pub(crate) fn new() -> Self {
+        Self {
+            aggregates: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, event: MetricEvent) {
+        let metric_type = event.metric_type;
+        let value = event.value;
+        let aggregate = self
+            .aggregates
+            .entry((event.name, metric_type, event.attributes))
+            .or_insert(Aggregate {
+                value: 0.0,
+                count: 0,
+                min: value,
+                max: value,
+            });
+
+        match metric_type {
+            MetricType::Counter | MetricType::Histogram => aggregate.value += value,
+            MetricType::Gauge => aggregate.value = value,
+        }
+        aggregate.count += 1;
+        aggregate.min = aggregate.min.min(value);
+        aggregate.max = aggregate.max.max(value);
+    }
+
+    pub(crate) fn drain(&mut self) -> Vec<MetricMessage> {
+        let timestamp = current_timestamp_rfc3339();
+        self.aggregates
+            .drain()
+            .map(|((name, metric_type, attributes), aggregate)| {
+                let is_histogram = metric_type == MetricType::Histogram;
+                MetricMessage {
+                    timestamp: timestamp.clone(),
+                    name,
+                    metric_type,
+                    value: aggregate.value,
+                    count: is_histogram.then_some(aggregate.count),
+                    min: is_histogram.then_some(aggregate.min),
+                    max: is_histogram.then_some(aggregate.max),
+                    attributes: attributes.into_iter().collect(),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,166 @@
+use crate::logger::{current_timestamp_rfc3339, Logger};
+use crate::types::{CheckInMessage, CheckInStatus};
+use crate::util::random_u64;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const MIN_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone)]
+pub struct Monitor {
+    logger: Logger,
+    slug: String,
+}
+
+impl Monitor {
+    pub(crate) fn new<S: Into<String>>(logger: Logger, slug: S) -> Self {
+        Self {
+            logger,
+            slug: slug.into(),
+        }
+    }
+
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub fn start(&self) -> CheckIn {
+        let check_in_id = new_check_in_id();
+        self.send(&check_in_id, CheckInStatus::InProgress, None, None);
+        CheckIn {
+            monitor: self.clone(),
+            check_in_id,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn ok(&self) {
+        self.send(&new_check_in_id(), CheckInStatus::Ok, None, None);
+    }
+
+    pub fn fail<E: Display + ?Sized>(&self, err: &E) {
+        self.send(
+            &new_check_in_id(),
+            CheckInStatus::Error,
+            None,
+            Some(err.to_string()),
+        );
+    }
+
+    pub fn run<T, E: Display>(&self, job: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let check_in = self.start();
+        let guard = PanicGuard(&check_in);
+        let result = job();
+        std::mem::forget(guard);
+        match &result {
+            Ok(_) => check_in.ok(),
+            Err(e) => check_in.fail(e),
+        }
+        result
+    }
+
+    pub fn heartbeat(&self, interval: Duration) -> Heartbeat {
+        let interval = interval.max(MIN_HEARTBEAT_INTERVAL);
+        let monitor = self.clone();
+        let (stop_tx, stop_rx) = channel();
+        let handle = thread::spawn(move || loop {
+            monitor.ok();
+            match stop_rx.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        });
+
+        Heartbeat {
+            stop_tx: Some(stop_tx),
+            handle: Some(handle),
+        }
+    }
+
+    fn send(
+        &self,
+        check_in_id: &str,
+        status: CheckInStatus,
+        duration_ms: Option<f64>,
+        error: Option<String>,
+    ) {
+        self.logger.record_check_in(CheckInMessage {
+            timestamp: current_timestamp_rfc3339(),
+            monitor: self.slug.clone(),
+            check_in_id: check_in_id.to_string(),
+            status,
+            duration_ms,
+            error,
+            attributes: HashMap::new(),
+        });
+    }
+}
+
+#[must_use = "call `ok` or `fail` to finish the check-in"]
+pub struct CheckIn {
+    monitor: Monitor,
+    check_in_id: String,
+    started: Instant,
+}
+
+impl CheckIn {
+    pub fn id(&self) -> &str {
+        &self.check_in_id
+    }
+
+    pub fn ok(self) {
+        self.finish(CheckInStatus::Ok, None);
+    }
+
+    pub fn fail<E: Display + ?Sized>(self, err: &E) {
+        self.finish(CheckInStatus::Error, Some(err.to_string()));
+    }
+
+    fn finish(&self, status: CheckInStatus, error: Option<String>) {
+        let duration_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+        self.monitor
+            .send(&self.check_in_id, status, Some(duration_ms), error);
+    }
+}
+
+struct PanicGuard<'a>(&'a CheckIn);
+
+impl Drop for PanicGuard<'_> {
+    fn drop(&mut self) {
+        self.0
+            .finish(CheckInStatus::Error, Some("job panicked".to_string()));
+    }
+}
+
+pub struct Heartbeat {
+    stop_tx: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Heartbeat {
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Heartbeat {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn new_check_in_id() -> String {
+    format!("{:016x}{:016x}", random_u64(), random_u64())
+}
@@ -0,0 +1,125 @@
+use crate::logger::{Logger, WeakLogger};
+use std::fs;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// /proc/self/stat reports CPU times in USER_HZ ticks. Linux fixes USER_HZ at
+// 100 on every architecture we support, which is what sysconf(_SC_CLK_TCK)
+// returns there.
+const CLOCK_TICKS_PER_SECOND: f64 = 100.0;
+
+pub(crate) struct ProcessCollector {
+    stop_tx: Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl ProcessCollector {
+    // The thread holds a weak handle, so dropping every `Logger` without a
+    // shutdown still frees the logger and ends the thread.
+    pub(crate) fn spawn(logger: WeakLogger, interval: Duration) -> Self {
+        let (stop_tx, stop_rx) = channel();
+        let handle = thread::spawn(move || loop {
+            let Some(stats) = sample() else {
+                eprintln!("Process metrics are unavailable: cannot read /proc/self");
+                break;
+            };
+            match logger.upgrade() {
+                Some(logger) => record(&logger, stats),
+                None => break,
+            }
+
+            match stop_rx.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        });
+
+        Self { stop_tx, handle }
+    }
+
+    pub(crate) fn stop(self) {
+        let _ = self.stop_tx.send(());
+        let _ = self.handle.join();
+    }
+}
+
+fn record(logger: &Logger, stats: ProcessStats) {
+    let metrics = logger.metrics();
+    metrics
+        .gauge("process.memory.rss_bytes")
+        .set(stats.rss_bytes);
+    metrics
+        .gauge("process.memory.virtual_bytes")
+        .set(stats.virtual_bytes);
+    metrics
+        .gauge("process.cpu.user_seconds")
+        .set(stats.cpu_user_seconds);
+    metrics
+        .gauge("process.cpu.system_seconds")
+        .set(stats.cpu_system_seconds);
+    metrics.gauge("process.open_fds").set(stats.open_fds);
+    metrics.gauge("process.threads").set(stats.threads);
+    metrics
+        .gauge("process.uptime_seconds")
+        .set(stats.uptime_seconds);
+}
+
+struct ProcessStats {
+    rss_bytes: f64,
+    virtual_bytes: f64,
+    cpu_user_seconds: f64,
+    cpu_system_seconds: f64,
+    open_fds: f64,
+    threads: f64,
+    uptime_seconds: f64,
+}
+
+fn sample() -> Option<ProcessStats> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let stat = fs::read_to_string("/proc/self/stat").ok()?;
+    let system_uptime: f64 = fs::read_to_string("/proc/uptime")
+        .ok()?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let ticks = |field: usize| -> Option<f64> {
+        fields
+            .get(field - 3)?
+            .parse::<f64>()
+            .ok()
+            .map(|t| t / CLOCK_TICKS_PER_SECOND)
+    };
+    let cpu_user_seconds = ticks(14)?;
+    let cpu_system_seconds = ticks(15)?;
+    let start_seconds = ticks(22)?;
+
+    // The directory handle used to list /proc/self/fd shows up in the listing.
+    let open_fds = fs::read_dir("/proc/self/fd")
+        .ok()?
+        .count()
+        .saturating_sub(1) as f64;
+
+    Some(ProcessStats {
+        rss_bytes: status_value(&status, "VmRSS")? * 1024.0,
+        virtual_bytes: status_value(&status, "VmSize")? * 1024.0,
+        cpu_user_seconds,
+        cpu_system_seconds,
+        open_fds,
+        threads: status_value(&status, "Threads")?,
+        uptime_seconds: (system_uptime - start_seconds).max(0.0),
+    })
+}
+
+fn status_value(status: &str, key: &str) -> Option<f64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
@@ -0,0 +1,70 @@
+use crate::types::{Attribute, LogRecord};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Keep,
+    Drop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessorStage {
+    CallingThread,
+    Worker,
+}
+
+pub trait Processor: Send + Sync {
+    fn process(&self, record: &mut LogRecord) -> Decision;
+}
+
+impl<F> Processor for F
+where
+    F: Fn(&mut LogRecord) -> Decision + Send + Sync,
+{
+    fn process(&self, record: &mut LogRecord) -> Decision {
+        self(record)
+    }
+}
+
+pub struct StaticAttributes {
+    attributes: Vec<Attribute>,
+}
+
+impl StaticAttributes {
+    pub fn new(attrs: impl IntoIterator<Item = Attribute>) -> Self {
+        Self {
+            attributes: attrs.into_iter().collect(),
+        }
+    }
+}
+
+impl Processor for StaticAttributes {
+    fn process(&self, record: &mut LogRecord) -> Decision {
+        for attr in &self.attributes {
+            record
+                .attributes
+                .entry(attr.key.clone())
+                .or_insert_with(|| attr.value.clone());
+        }
+        Decision::Keep
+    }
+}
+
+pub(crate) struct Pipeline {
+    pub(crate) stage: ProcessorStage,
+    processors: Vec<Box<dyn Processor>>,
+}
+
+impl Pipeline {
+    pub(crate) fn new(stage: ProcessorStage, processors: Vec<Box<dyn Processor>>) -> Self {
+        Self { stage, processors }
+    }
+
+    pub(crate) fn run(&self, record: &mut LogRecord) -> Decision {
+        for processor in &self.processors {
+            if processor.process(record) == Decision::Drop {
+                return Decision::Drop;
+            }
+        }
+        Decision::Keep
+    }
+}
@@ -0,0 +1,343 @@
+use crate::logger::current_timestamp_rfc3339;
+use crate::types::LogRecord;
+use crate::util::fnv1a64;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct Suppressed {
+    count: u64,
+    first_timestamp: String,
+    last_timestamp: String,
+    since: Instant,
+    sample: LogRecord,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+    suppressed: Option<Suppressed>,
+}
+
+struct State {
+    buckets: HashMap<u64, Bucket>,
+    last_summary: Instant,
+}
+
+pub struct RateLimiter {
+    burst: f64,
+    rate: f64,
+    summary_interval: Duration,
+    max_keys: usize,
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    pub fn builder() -> RateLimiterBuilder {
+        RateLimiterBuilder::new()
+    }
+
+    pub(crate) fn allow(&self, record: &LogRecord) -> bool {
+        let key = limiter_key(record);
+        let now = Instant::now();
+        let Ok(mut state) = self.state.lock() else {
+            return true;
+        };
+
+        if !state.buckets.contains_key(&key) && state.buckets.len() >= self.max_keys {
+            evict_stalest(&mut state.buckets);
+        }
+        let bucket = state.buckets.entry(key).or_insert(Bucket {
+            tokens: self.burst,
+            last_refill: now,
+            suppressed: None,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return true;
+        }
+
+        let timestamp = record.timestamp.clone();
+        match &mut bucket.suppressed {
+            Some(suppressed) => {
+                suppressed.count += 1;
+                suppressed.last_timestamp = timestamp;
+            }
+            None => {
+                bucket.suppressed = Some(Suppressed {
+                    count: 1,
+                    first_timestamp: timestamp.clone(),
+                    last_timestamp: timestamp,
+                    since: now,
+                    sample: record.clone(),
+                });
+            }
+        }
+        false
+    }
+
+    pub(crate) fn take_summaries(&self, force: bool) -> Vec<LogRecord> {
+        let now = Instant::now();
+        let Ok(mut state) = self.state.lock() else {
+            return Vec::new();
+        };
+        if !force && now.duration_since(state.last_summary) < self.summary_interval {
+            return Vec::new();
+        }
+        state.last_summary = now;
+
+        let mut summaries = Vec::new();
+        let idle_after = Duration::try_from_secs_f64(self.burst / self.rate.max(f64::EPSILON))
+            .unwrap_or(Duration::MAX);
+        state.buckets.retain(|_, bucket| {
+            if let Some(suppressed) = bucket.suppressed.take() {
+                summaries.push(summarize(suppressed, now));
+                return true;
+            }
+            now.duration_since(bucket.last_refill) < idle_after
+        });
+        summaries
+    }
+}
+
+fn evict_stalest(buckets: &mut HashMap<u64, Bucket>) {
+    let stalest = buckets
+        .iter()
+        .min_by_key(|(_, bucket)| (bucket.suppressed.is_some(), bucket.last_refill))
+        .map(|(key, _)| *key);
+    if let Some(key) = stalest {
+        buckets.remove(&key);
+    }
+}
+
+fn limiter_key(record: &LogRecord) -> u64 {
+    let template = record
+        .attributes
+        .get("message.template")
+        .unwrap_or(&record.body);
+    let file = record.attributes.get("file").map_or("", String::as_str);
+    let line = record.attributes.get("line").map_or("", String::as_str);
+    let key = format!("{:?}\u{0}{}\u{0}{}:{}", record.level, template, file, line);
+    fnv1a64(key.as_bytes())
+}
+
+fn summarize(suppressed: Suppressed, now: Instant) -> LogRecord {
+    let Suppressed {
+        count,
+        first_timestamp,
+        last_timestamp,
+        since,
+        mut sample,
+    } = suppressed;
+
+    let template = sample
+        .attributes
+        .get("message.template")
+        .cloned()
+        .unwrap_or_else(|| sample.body.clone());
+    let window = now.duration_since(since).as_secs().max(1);
+
+    sample.body = format!(
+        "message \"{}\" repeated {} times in {}s",
+        template,
+        group_thousands(count),
+        window
+    );
+    sample.timestamp = current_timestamp_rfc3339();
+    let attributes = &mut sample.attributes;
+    attributes.insert("suppressed.count".to_string(), count.to_string());
+    attributes.insert("suppressed.first_timestamp".to_string(), first_timestamp);
+    attributes.insert("suppressed.last_timestamp".to_string(), last_timestamp);
+    attributes.insert("suppressed.summary".to_string(), "true".to_string());
+    sample
+}
+
+pub struct RateLimiterBuilder {
+    burst: u32,
+    rate: f64,
+    summary_interval: Duration,
+    max_keys: usize,
+}
+
+impl Default for RateLimiterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimiterBuilder {
+    pub fn new() -> Self {
+        Self {
+            burst: 10,
+            rate: 1.0,
+            summary_interval: Duration::from_secs(10),
+            max_keys: 10_000,
+        }
+    }
+
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    pub fn rate(mut self, per_second: f64) -> Self {
+        self.rate = per_second.max(0.0);
+        self
+    }
+
+    pub fn summary_interval(mut self, interval: Duration) -> Self {
+        self.summary_interval = interval;
+        self
+    }
+
+    pub fn max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys.max(1);
+        self
+    }
+
+    pub fn build(self) -> RateLimiter {
+        RateLimiter {
+            burst: f64::from(self.burst),
+            rate: self.rate,
+            summary_interval: self.summary_interval,
+            max_keys: self.max_keys,
+            state: Mutex::new(State {
+                buckets: HashMap::new(),
+                last_summary: Instant::now(),
+            }),
+        }
+    }
+}
+
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LogLevel;
+
+    fn record(level: LogLevel, body: &str) -> LogRecord {
+        LogRecord {
+            timestamp: body.to_string(),
+            body: body.to_string(),
+            level,
+            attributes: HashMap::new(),
+        }
+    }
+
+    fn limiter(burst: u32, rate: f64) -> RateLimiter {
+        RateLimiter::builder().burst(burst).rate(rate).build()
+    }
+
+    #[test]
+    fn allows_a_burst_then_suppresses() {
+        let limiter = limiter(2, 0.0);
+        let allowed: Vec<bool> = (0..4)
+            .map(|_| limiter.allow(&record(LogLevel::INFO, "hello")))
+            .collect();
+        assert_eq!(allowed, [true, true, false, false]);
+    }
+
+    #[test]
+    fn keys_by_level_and_template() {
+        let limiter = limiter(1, 0.0);
+        assert!(limiter.allow(&record(LogLevel::INFO, "a")));
+        assert!(limiter.allow(&record(LogLevel::WARNING, "a")));
+        assert!(limiter.allow(&record(LogLevel::INFO, "b")));
+        assert!(!limiter.allow(&record(LogLevel::INFO, "a")));
+
+        let mut templated = record(LogLevel::INFO, "user 2 logged in");
+        templated.attributes.insert(
+            "message.template".to_string(),
+            "user {id} logged in".to_string(),
+        );
+        assert!(limiter.allow(&templated));
+        templated.body = "user 3 logged in".to_string();
+        assert!(!limiter.allow(&templated));
+    }
+
+    #[test]
+    fn tokens_refill_over_time() {
+        let limiter = limiter(1, 10.0);
+        assert!(limiter.allow(&record(LogLevel::INFO, "a")));
+        assert!(!limiter.allow(&record(LogLevel::INFO, "a")));
+        for bucket in limiter.state.lock().unwrap().buckets.values_mut() {
+            bucket.last_refill -= Duration::from_millis(200);
+        }
+        assert!(limiter.allow(&record(LogLevel::INFO, "a")));
+    }
+
+    #[test]
+    fn summaries_report_suppressed_records() {
+        let limiter = limiter(1, 0.0);
+        for body in ["first", "first", "first", "first"] {
+            limiter.allow(&record(LogLevel::INFO, body));
+        }
+        assert!(limiter.take_summaries(false).is_empty());
+
+        let summaries = limiter.take_summaries(true);
+        assert_eq!(summaries.len(), 1);
+        let summary = &summaries[0];
+        assert!(summary
+            .body
+            .starts_with("message \"first\" repeated 3 times in "));
+        assert_eq!(summary.attributes["suppressed.count"], "3");
+        assert_eq!(summary.attributes["suppressed.summary"], "true");
+        assert_eq!(summary.attributes["suppressed.first_timestamp"], "first");
+
+        assert!(limiter.take_summaries(true).is_empty());
+    }
+
+    #[test]
+    fn bucket_count_is_capped() {
+        let limiter = RateLimiter::builder()
+            .burst(1)
+            .rate(0.0)
+            .max_keys(3)
+            .build();
+        for i in 0..100 {
+            limiter.allow(&record(LogLevel::INFO, &i.to_string()));
+        }
+        assert_eq!(limiter.state.lock().unwrap().buckets.len(), 3);
+    }
+
+    #[test]
+    fn eviction_keeps_buckets_with_pending_summaries() {
+        let limiter = RateLimiter::builder()
+            .burst(1)
+            .rate(0.0)
+            .max_keys(2)
+            .build();
+        limiter.allow(&record(LogLevel::INFO, "noisy"));
+        limiter.allow(&record(LogLevel::INFO, "noisy"));
+        for i in 0..10 {
+            limiter.allow(&record(LogLevel::INFO, &i.to_string()));
+        }
+        let summaries = limiter.take_summaries(true);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].attributes["suppressed.count"], "1");
+    }
+
+    #[test]
+    fn group_thousands_inserts_separators() {
+        assert_eq!(group_thousands(0), "0");
+        assert_eq!(group_thousands(999), "999");
+        assert_eq!(group_thousands(1_000), "1,000");
+        assert_eq!(group_thousands(1_234_567), "1,234,567");
+    }
+}
@@ -0,0 +1,372 @@
+use crate::error::VigilantError;
+use crate::processor::{Decision, Processor};
+use crate::types::LogRecord;
+use regex::{Captures, Regex};
+use sha2::{Digest, Sha256};
+
+pub const MASK: &str = "[REDACTED]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionMode {
+    Mask,
+    Hash,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detector {
+    Email,
+    CardNumber,
+    BearerToken,
+}
+
+impl Detector {
+    pub const ALL: [Detector; 3] = [Detector::Email, Detector::CardNumber, Detector::BearerToken];
+
+    fn pattern(self) -> &'static str {
+        match self {
+            Detector::Email => r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+            Detector::CardNumber => r"\b(?:\d[ -]?){12,18}\d\b",
+            Detector::BearerToken => r"(?i)\bbearer\s+[A-Za-z0-9\-._~+/]+=*",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Pattern {
+    regex: Regex,
+    luhn: bool,
+}
+
+#[derive(Clone)]
+pub struct Redactor {
+    mode: RedactionMode,
+    hash_salt: String,
+    deny_keys: Vec<String>,
+    patterns: Vec<Pattern>,
+}
+
+impl Redactor {
+    pub fn builder() -> RedactorBuilder {
+        RedactorBuilder::new()
+    }
+
+    fn redact(&self, message: &mut LogRecord) {
+        message.body = self.redact_value(&message.body);
+        self.redact_attributes(message.attributes.iter_mut());
+    }
+
+    pub(crate) fn redact_attributes<'a>(
+        &self,
+        attributes: impl IntoIterator<Item = (&'a String, &'a mut String)>,
+    ) {
+        for (key, value) in attributes {
+            *value = self.redact_attribute(key, value);
+        }
+    }
+
+    pub(crate) fn redact_attribute(&self, key: &str, value: &str) -> String {
+        if key == "service.name" {
+            value.to_string()
+        } else if self.is_denied(key) {
+            self.replacement(value)
+        } else {
+            self.redact_value(value)
+        }
+    }
+
+    fn is_denied(&self, key: &str) -> bool {
+        let key = key.to_ascii_lowercase();
+        self.deny_keys.iter().any(|denied| {
+            key == *denied
+                || key
+                    .strip_suffix(denied.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
+
+    pub(crate) fn redact_value(&self, value: &str) -> String {
+        let mut value = value.to_string();
+        for pattern in &self.patterns {
+            if !pattern.regex.is_match(&value) {
+                continue;
+            }
+            value = pattern
+                .regex
+                .replace_all(&value, |caps: &Captures| {
+                    let matched = &caps[0];
+                    if pattern.luhn && !luhn_valid(matched) {
+                        matched.to_string()
+                    } else {
+                        self.replacement(matched)
+                    }
+                })
+                .into_owned();
+        }
+        value
+    }
+
+    fn replacement(&self, value: &str) -> String {
+        match self.mode {
+            RedactionMode::Mask => MASK.to_string(),
+            RedactionMode::Hash => {
+                let mut hasher = Sha256::new();
+                hasher.update(self.hash_salt.as_bytes());
+                hasher.update(value.as_bytes());
+                let digest = hasher.finalize();
+                let hex: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+                format!("[HASH:{}]", hex)
+            }
+        }
+    }
+}
+
+impl Processor for Redactor {
+    fn process(&self, record: &mut LogRecord) -> Decision {
+        self.redact(record);
+        Decision::Keep
+    }
+}
+
+pub struct RedactorBuilder {
+    mode: RedactionMode,
+    hash_salt: String,
+    deny_keys: Vec<String>,
+    patterns: Vec<String>,
+    detectors: Vec<Detector>,
+}
+
+impl Default for RedactorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedactorBuilder {
+    pub fn new() -> Self {
+        Self {
+            mode: RedactionMode::Mask,
+            hash_salt: String::new(),
+            deny_keys: Vec::new(),
+            patterns: Vec::new(),
+            detectors: Vec::new(),
+        }
+    }
+
+    pub fn mode(mut self, mode: RedactionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn hash_salt<S: Into<String>>(mut self, salt: S) -> Self {
+        self.hash_salt = salt.into();
+        self
+    }
+
+    pub fn deny_key<S: Into<String>>(mut self, key: S) -> Self {
+        self.deny_keys.push(key.into().to_ascii_lowercase());
+        self
+    }
+
+    pub fn deny_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.deny_keys
+            .extend(keys.into_iter().map(|k| k.into().to_ascii_lowercase()));
+        self
+    }
+
+    pub fn pattern<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    pub fn detect(mut self, detector: Detector) -> Self {
+        if !self.detectors.contains(&detector) {
+            self.detectors.push(detector);
+        }
+        self
+    }
+
+    pub fn detect_all(mut self) -> Self {
+        self.detectors = Detector::ALL.to_vec();
+        self
+    }
+
+    pub fn build(self) -> Result<Redactor, VigilantError> {
+        let mut patterns = Vec::with_capacity(self.detectors.len() + self.patterns.len());
+
+        for detector in self.detectors {
+            patterns.push(Pattern {
+                regex: compile(detector.pattern())?,
+                luhn: detector == Detector::CardNumber,
+            });
+        }
+        for pattern in &self.patterns {
+            patterns.push(Pattern {
+                regex: compile(pattern)?,
+                luhn: false,
+            });
+        }
+
+        Ok(Redactor {
+            mode: self.mode,
+            hash_salt: self.hash_salt,
+            deny_keys: self.deny_keys,
+            patterns,
+        })
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex, VigilantError> {
+    Regex::new(pattern).map_err(|e| VigilantError::InvalidPattern(e.to_string()))
+}
+
+fn luhn_valid(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() < 13 {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LogLevel;
+    use std::collections::HashMap;
+
+    fn redactor(builder: RedactorBuilder) -> Redactor {
+        builder.build().expect("valid redactor")
+    }
+
+    #[test]
+    fn luhn_accepts_valid_numbers_only() {
+        assert!(luhn_valid("4111 1111 1111 1111"));
+        assert!(luhn_valid("5500-0000-0000-0004"));
+        assert!(!luhn_valid("4111 1111 1111 1112"));
+        assert!(!luhn_valid("4111 1111 11"));
+    }
+
+    #[test]
+    fn email_detector_masks_addresses() {
+        let redactor = redactor(Redactor::builder().detect(Detector::Email));
+        assert_eq!(
+            redactor.redact_value("contact jane.doe+x@example.co.uk now"),
+            "contact [REDACTED] now"
+        );
+    }
+
+    #[test]
+    fn card_detector_masks_luhn_valid_numbers_only() {
+        let redactor = redactor(Redactor::builder().detect(Detector::CardNumber));
+        assert_eq!(
+            redactor.redact_value("paid with 4111-1111-1111-1111"),
+            "paid with [REDACTED]"
+        );
+        assert_eq!(
+            redactor.redact_value("order 4111111111111112"),
+            "order 4111111111111112"
+        );
+    }
+
+    #[test]
+    fn bearer_detector_masks_tokens() {
+        let redactor = redactor(Redactor::builder().detect(Detector::BearerToken));
+        assert_eq!(
+            redactor.redact_value("Authorization: Bearer abc.DEF-123=="),
+            "Authorization: [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn custom_patterns_are_applied() {
+        let redactor = redactor(Redactor::builder().pattern(r"sk_[a-z0-9]+"));
+        assert_eq!(redactor.redact_value("key sk_live42"), "key [REDACTED]");
+    }
+
+    #[test]
+    fn invalid_pattern_is_rejected() {
+        assert!(matches!(
+            Redactor::builder().pattern("(").build(),
+            Err(VigilantError::InvalidPattern(_))
+        ));
+    }
+
+    #[test]
+    fn deny_keys_match_exactly_or_as_dotted_suffix() {
+        let redactor = redactor(Redactor::builder().deny_key("Authorization"));
+        assert_eq!(redactor.redact_attribute("authorization", "x"), MASK);
+        assert_eq!(
+            redactor.redact_attribute("http.header.AUTHORIZATION", "x"),
+            MASK
+        );
+        assert_eq!(redactor.redact_attribute("xauthorization", "x"), "x");
+        assert_eq!(redactor.redact_attribute("authorization.kind", "x"), "x");
+    }
+
+    #[test]
+    fn hash_mode_is_salted_and_stable() {
+        let salted = |salt: &str| {
+            redactor(
+                Redactor::builder()
+                    .mode(RedactionMode::Hash)
+                    .hash_salt(salt)
+                    .deny_key("user"),
+            )
+        };
+        let first = salted("a").redact_attribute("user", "jane");
+        assert!(first.starts_with("[HASH:") && first.ends_with(']'));
+        assert_eq!(first.len(), "[HASH:]".len() + 16);
+        assert_eq!(salted("a").redact_attribute("user", "jane"), first);
+        assert_ne!(salted("b").redact_attribute("user", "jane"), first);
+        assert_ne!(salted("a").redact_attribute("user", "john"), first);
+    }
+
+    #[test]
+    fn service_name_is_never_redacted() {
+        let redactor = redactor(Redactor::builder().deny_key("name").pattern("checkout"));
+        assert_eq!(
+            redactor.redact_attribute("service.name", "checkout"),
+            "checkout"
+        );
+        assert_eq!(redactor.redact_attribute("user.name", "checkout"), MASK);
+    }
+
+    #[test]
+    fn processor_redacts_body_and_attributes() {
+        let redactor = redactor(Redactor::builder().detect_all().deny_key("password"));
+        let mut record = LogRecord {
+            timestamp: String::new(),
+            body: "login from jane@example.com".to_string(),
+            level: LogLevel::INFO,
+            attributes: HashMap::from([
+                ("password".to_string(), "hunter2".to_string()),
+                ("note".to_string(), "Bearer abc".to_string()),
+            ]),
+        };
+        assert_eq!(redactor.process(&mut record), Decision::Keep);
+        assert_eq!(record.body, "login from [REDACTED]");
+        assert_eq!(record.attributes["password"], MASK);
+        assert_eq!(record.attributes["note"], MASK);
+    }
+}
@@ -0,0 +1,320 @@
+use crate::processor::{Decision, Processor};
+use crate::types::{LogLevel, LogRecord};
+use crate::util::{fnv1a64, mix64, random_u64, unit_interval};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SamplingKey {
+    Random,
+    TraceId,
+    Attribute(String),
+}
+
+struct TargetRule {
+    prefix: String,
+    ratio: f64,
+}
+
+struct AttributeRule {
+    key: String,
+    value: String,
+    ratio: f64,
+}
+
+pub struct Sampler {
+    level_ratios: [f64; 3],
+    target_rules: Vec<TargetRule>,
+    attribute_rules: Vec<AttributeRule>,
+    key: SamplingKey,
+}
+
+impl Sampler {
+    pub fn builder() -> SamplerBuilder {
+        SamplerBuilder::new()
+    }
+
+    fn ratio_for(&self, record: &LogRecord) -> f64 {
+        for rule in &self.attribute_rules {
+            if record.attributes.get(&rule.key) == Some(&rule.value) {
+                return rule.ratio;
+            }
+        }
+
+        let target = record
+            .attributes
+            .get("target")
+            .or_else(|| record.attributes.get("module_path"));
+        if let Some(target) = target {
+            if let Some(rule) = self
+                .target_rules
+                .iter()
+                .filter(|rule| target.starts_with(&rule.prefix))
+                .max_by_key(|rule| rule.prefix.len())
+            {
+                return rule.ratio;
+            }
+        }
+
+        match record.level {
+            LogLevel::DEBUG => self.level_ratios[0],
+            LogLevel::INFO => self.level_ratios[1],
+            LogLevel::WARNING => self.level_ratios[2],
+            LogLevel::ERROR | LogLevel::FATAL => 1.0,
+        }
+    }
+
+    fn roll(&self, record: &LogRecord) -> f64 {
+        let key_value = match &self.key {
+            SamplingKey::Random => None,
+            SamplingKey::TraceId => record.attributes.get("trace.id"),
+            SamplingKey::Attribute(key) => record.attributes.get(key),
+        };
+
+        match key_value {
+            Some(value) => unit_interval(mix64(fnv1a64(value.as_bytes()))),
+            None => unit_interval(random_u64()),
+        }
+    }
+}
+
+impl Processor for Sampler {
+    fn process(&self, record: &mut LogRecord) -> Decision {
+        if matches!(record.level, LogLevel::ERROR | LogLevel::FATAL) {
+            return Decision::Keep;
+        }
+
+        let ratio = self.ratio_for(record).clamp(0.0, 1.0);
+        if ratio >= 1.0 {
+            return Decision::Keep;
+        }
+        if ratio <= 0.0 || self.roll(record) >= ratio {
+            return Decision::Drop;
+        }
+
+        record
+            .attributes
+            .insert("sample.rate".to_string(), ratio.to_string());
+        Decision::Keep
+    }
+}
+
+pub struct SamplerBuilder {
+    level_ratios: [f64; 3],
+    target_rules: Vec<TargetRule>,
+    attribute_rules: Vec<AttributeRule>,
+    key: SamplingKey,
+}
+
+impl Default for SamplerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SamplerBuilder {
+    pub fn new() -> Self {
+        Self {
+            level_ratios: [1.0; 3],
+            target_rules: Vec::new(),
+            attribute_rules: Vec::new(),
+            key: SamplingKey::Random,
+        }
+    }
+
+    pub fn level_ratio(mut self, level: LogLevel, ratio: f64) -> Self {
+        match level {
+            LogLevel::DEBUG => self.level_ratios[0] = valid_ratio(ratio),
+            LogLevel::INFO => self.level_ratios[1] = valid_ratio(ratio),
+            LogLevel::WARNING => self.level_ratios[2] = valid_ratio(ratio),
+            LogLevel::ERROR | LogLevel::FATAL => {}
+        }
+        self
+    }
+
+    pub fn target_ratio<S: Into<String>>(mut self, target_prefix: S, ratio: f64) -> Self {
+        self.target_rules.push(TargetRule {
+            prefix: target_prefix.into(),
+            ratio: valid_ratio(ratio),
+        });
+        self
+    }
+
+    pub fn attribute_ratio<K: Into<String>, V: Into<String>>(
+        mut self,
+        key: K,
+        value: V,
+        ratio: f64,
+    ) -> Self {
+        self.attribute_rules.push(AttributeRule {
+            key: key.into(),
+            value: value.into(),
+            ratio: valid_ratio(ratio),
+        });
+        self
+    }
+
+    pub fn key(mut self, key: SamplingKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn build(self) -> Sampler {
+        Sampler {
+            level_ratios: self.level_ratios,
+            target_rules: self.target_rules,
+            attribute_rules: self.attribute_rules,
+            key: self.key,
+        }
+    }
+}
+
+fn valid_ratio(ratio: f64) -> f64 {
+    if ratio.is_finite() {
+        ratio.clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn record(level: LogLevel, attributes: &[(&str, &str)]) -> LogRecord {
+        LogRecord {
+            timestamp: String::new(),
+            body: String::new(),
+            level,
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    fn kept(sampler: &Sampler, mut record: LogRecord) -> bool {
+        sampler.process(&mut record) == Decision::Keep
+    }
+
+    #[test]
+    fn errors_are_always_kept() {
+        let sampler = Sampler::builder()
+            .attribute_ratio("route", "/health", 0.0)
+            .build();
+        for level in [LogLevel::ERROR, LogLevel::FATAL] {
+            assert!(kept(&sampler, record(level, &[("route", "/health")])));
+        }
+    }
+
+    #[test]
+    fn level_ratios_apply_per_level() {
+        let sampler = Sampler::builder()
+            .level_ratio(LogLevel::DEBUG, 0.0)
+            .level_ratio(LogLevel::INFO, 1.0)
+            .build();
+        assert!(!kept(&sampler, record(LogLevel::DEBUG, &[])));
+        assert!(kept(&sampler, record(LogLevel::INFO, &[])));
+        assert!(kept(&sampler, record(LogLevel::WARNING, &[])));
+    }
+
+    #[test]
+    fn attribute_rules_win_over_target_rules_and_longest_target_prefix_wins() {
+        let sampler = Sampler::builder()
+            .target_ratio("app", 0.0)
+            .target_ratio("app::api", 1.0)
+            .attribute_ratio("route", "/health", 0.0)
+            .build();
+        assert!(!kept(
+            &sampler,
+            record(LogLevel::INFO, &[("target", "app::db")])
+        ));
+        assert!(kept(
+            &sampler,
+            record(LogLevel::INFO, &[("target", "app::api::users")])
+        ));
+        assert!(!kept(
+            &sampler,
+            record(
+                LogLevel::INFO,
+                &[("target", "app::api"), ("route", "/health")]
+            )
+        ));
+    }
+
+    #[test]
+    fn kept_records_carry_the_sample_rate() {
+        let sampler = Sampler::builder()
+            .level_ratio(LogLevel::INFO, 0.5)
+            .key(SamplingKey::TraceId)
+            .build();
+        let kept_record = (0..100)
+            .map(|i| {
+                let mut record = record(LogLevel::INFO, &[("trace.id", &i.to_string())]);
+                let decision = sampler.process(&mut record);
+                (decision, record)
+            })
+            .find(|(decision, _)| *decision == Decision::Keep)
+            .map(|(_, record)| record)
+            .expect("some trace is kept at 50%");
+        assert_eq!(kept_record.attributes["sample.rate"], "0.5");
+    }
+
+    #[test]
+    fn non_finite_ratios_keep_everything() {
+        let sampler = Sampler::builder()
+            .level_ratio(LogLevel::INFO, f64::NAN)
+            .target_ratio("app", f64::INFINITY)
+            .build();
+        let mut info = record(LogLevel::INFO, &[]);
+        assert_eq!(sampler.process(&mut info), Decision::Keep);
+        assert!(!info.attributes.contains_key("sample.rate"));
+        assert!(kept(
+            &sampler,
+            record(LogLevel::DEBUG, &[("target", "app")])
+        ));
+    }
+
+    #[test]
+    fn trace_id_key_samples_whole_traces() {
+        let sampler = Sampler::builder()
+            .level_ratio(LogLevel::DEBUG, 0.5)
+            .level_ratio(LogLevel::INFO, 0.5)
+            .key(SamplingKey::TraceId)
+            .build();
+        let mut kept_traces = 0;
+        for trace in 0..200 {
+            let trace_id = format!("{:032x}", trace);
+            let decisions: Vec<bool> = [LogLevel::DEBUG, LogLevel::INFO, LogLevel::DEBUG]
+                .into_iter()
+                .map(|level| kept(&sampler, record(level, &[("trace.id", &trace_id)])))
+                .collect();
+            assert!(decisions.iter().all(|d| *d == decisions[0]), "{}", trace_id);
+            kept_traces += usize::from(decisions[0]);
+        }
+        assert!((50..150).contains(&kept_traces), "{}", kept_traces);
+    }
+
+    #[test]
+    fn attribute_key_samples_whole_requests() {
+        let sampler = Sampler::builder()
+            .level_ratio(LogLevel::INFO, 0.3)
+            .key(SamplingKey::Attribute("request.id".to_string()))
+            .build();
+        for request in 0..100 {
+            let request_id = request.to_string();
+            let first = kept(
+                &sampler,
+                record(LogLevel::INFO, &[("request.id", &request_id)]),
+            );
+            for _ in 0..5 {
+                assert_eq!(
+                    kept(
+                        &sampler,
+                        record(LogLevel::INFO, &[("request.id", &request_id)])
+                    ),
+                    first
+                );
+            }
+        }
+    }
+}
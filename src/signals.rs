@@ -0,0 +1,75 @@
+use crate::logger::Logger;
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::{Handle, Signals};
+use signal_hook::low_level::emulate_default_handler;
+use std::thread;
+use std::time::Duration;
+
+pub struct SignalFlush {
+    handle: Handle,
+    worker_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl SignalFlush {
+    pub fn builder() -> SignalFlushBuilder {
+        SignalFlushBuilder::new()
+    }
+
+    pub fn uninstall(mut self) {
+        self.handle.close();
+        if let Some(h) = self.worker_handle.take() {
+            let _ = h.join();
+        }
+    }
+}
+
+pub struct SignalFlushBuilder {
+    grace_period: Duration,
+    reraise: bool,
+}
+
+impl Default for SignalFlushBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalFlushBuilder {
+    pub fn new() -> Self {
+        Self {
+            grace_period: Duration::from_secs(5),
+            reraise: true,
+        }
+    }
+
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    pub fn reraise(mut self, enabled: bool) -> Self {
+        self.reraise = enabled;
+        self
+    }
+
+    pub fn install(self) -> std::io::Result<SignalFlush> {
+        let mut signals = Signals::new([SIGTERM, SIGINT])?;
+        let handle = signals.handle();
+
+        let worker_handle = thread::spawn(move || {
+            for signal in signals.forever() {
+                if let Err(e) = Logger::flush_all(self.grace_period) {
+                    eprintln!("Failed to flush logs on signal {}: {}", signal, e);
+                }
+                if self.reraise {
+                    let _ = emulate_default_handler(signal);
+                }
+            }
+        });
+
+        Ok(SignalFlush {
+            handle,
+            worker_handle: Some(worker_handle),
+        })
+    }
+}
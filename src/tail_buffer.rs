@@ -0,0 +1,294 @@
+use crate::types::{LogLevel, LogRecord};
+use crate::util::random_u64;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+thread_local! {
+    static SCOPE_STACK: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+pub(crate) fn current_scope_id() -> Option<String> {
+    SCOPE_STACK.with(|stack| stack.borrow().last().cloned())
+}
+
+pub(crate) struct ScopeGuard {
+    pub(crate) id: String,
+}
+
+impl ScopeGuard {
+    pub(crate) fn enter() -> Self {
+        let id = format!("{:016x}", random_u64());
+        SCOPE_STACK.with(|stack| stack.borrow_mut().push(id.clone()));
+        Self { id }
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPE_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack.iter().rposition(|id| *id == self.id) {
+                stack.remove(pos);
+            }
+        });
+    }
+}
+
+struct Buffers {
+    records: HashMap<String, VecDeque<LogRecord>>,
+    order: VecDeque<String>,
+}
+
+pub struct TailBuffer {
+    threshold: LogLevel,
+    trigger: LogLevel,
+    capacity: usize,
+    max_keys: usize,
+    buffers: Mutex<Buffers>,
+}
+
+impl TailBuffer {
+    pub fn builder() -> TailBufferBuilder {
+        TailBufferBuilder::new()
+    }
+
+    pub(crate) fn handle(&self, record: LogRecord) -> Vec<LogRecord> {
+        let Some(key) = buffer_key(&record) else {
+            return vec![record];
+        };
+
+        let Ok(mut buffers) = self.buffers.lock() else {
+            return vec![record];
+        };
+
+        let severity = record.level.severity();
+        if severity < self.threshold.severity() {
+            if !buffers.records.contains_key(&key) {
+                if buffers.order.len() >= self.max_keys {
+                    if let Some(oldest) = buffers.order.pop_front() {
+                        buffers.records.remove(&oldest);
+                    }
+                }
+                buffers.order.push_back(key.clone());
+            }
+            let ring = buffers.records.entry(key).or_default();
+            if ring.len() >= self.capacity {
+                ring.pop_front();
+            }
+            ring.push_back(record);
+            return Vec::new();
+        }
+
+        if severity < self.trigger.severity() {
+            return vec![record];
+        }
+
+        let mut flushed: Vec<LogRecord> = match buffers.records.remove(&key) {
+            Some(ring) => {
+                buffers.order.retain(|k| *k != key);
+                ring.into_iter()
+                    .map(|mut buffered| {
+                        buffered
+                            .attributes
+                            .insert("tail.buffered".to_string(), "true".to_string());
+                        buffered
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        flushed.push(record);
+        flushed
+    }
+
+    pub(crate) fn discard(&self, key: &str) {
+        if let Ok(mut buffers) = self.buffers.lock() {
+            if buffers.records.remove(key).is_some() {
+                buffers.order.retain(|k| k != key);
+            }
+        }
+    }
+}
+
+fn buffer_key(record: &LogRecord) -> Option<String> {
+    record
+        .attributes
+        .get("trace.id")
+        .or_else(|| record.attributes.get("scope.id"))
+        .cloned()
+}
+
+pub struct TailBufferBuilder {
+    threshold: LogLevel,
+    trigger: LogLevel,
+    capacity: usize,
+    max_keys: usize,
+}
+
+impl Default for TailBufferBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TailBufferBuilder {
+    pub fn new() -> Self {
+        Self {
+            threshold: LogLevel::INFO,
+            trigger: LogLevel::ERROR,
+            capacity: 100,
+            max_keys: 1024,
+        }
+    }
+
+    pub fn threshold(mut self, level: LogLevel) -> Self {
+        self.threshold = level;
+        self
+    }
+
+    pub fn trigger(mut self, level: LogLevel) -> Self {
+        self.trigger = level;
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys.max(1);
+        self
+    }
+
+    pub fn build(self) -> TailBuffer {
+        let threshold = if self.threshold.severity() > self.trigger.severity() {
+            self.trigger
+        } else {
+            self.threshold
+        };
+        TailBuffer {
+            threshold,
+            trigger: self.trigger,
+            capacity: self.capacity,
+            max_keys: self.max_keys,
+            buffers: Mutex::new(Buffers {
+                records: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: LogLevel, body: &str, key: &str) -> LogRecord {
+        LogRecord {
+            timestamp: String::new(),
+            body: body.to_string(),
+            level,
+            attributes: HashMap::from([("scope.id".to_string(), key.to_string())]),
+        }
+    }
+
+    fn bodies(records: &[LogRecord]) -> Vec<&str> {
+        records.iter().map(|record| record.body.as_str()).collect()
+    }
+
+    #[test]
+    fn records_below_threshold_are_buffered() {
+        let buffer = TailBuffer::builder().build();
+        assert!(buffer.handle(record(LogLevel::DEBUG, "a", "s1")).is_empty());
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::INFO, "b", "s1"))),
+            ["b"]
+        );
+    }
+
+    #[test]
+    fn records_without_a_key_pass_through() {
+        let buffer = TailBuffer::builder().build();
+        let mut unkeyed = record(LogLevel::DEBUG, "a", "s1");
+        unkeyed.attributes.clear();
+        assert_eq!(bodies(&buffer.handle(unkeyed)), ["a"]);
+    }
+
+    #[test]
+    fn trigger_flushes_the_buffer_of_its_key() {
+        let buffer = TailBuffer::builder().build();
+        buffer.handle(record(LogLevel::DEBUG, "a", "s1"));
+        buffer.handle(record(LogLevel::DEBUG, "b", "s1"));
+        buffer.handle(record(LogLevel::DEBUG, "other", "s2"));
+
+        let flushed = buffer.handle(record(LogLevel::ERROR, "boom", "s1"));
+        assert_eq!(bodies(&flushed), ["a", "b", "boom"]);
+        assert_eq!(flushed[0].attributes["tail.buffered"], "true");
+        assert_eq!(flushed[1].attributes["tail.buffered"], "true");
+        assert!(!flushed[2].attributes.contains_key("tail.buffered"));
+
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::ERROR, "again", "s1"))),
+            ["again"]
+        );
+    }
+
+    #[test]
+    fn ring_keeps_the_most_recent_records() {
+        let buffer = TailBuffer::builder().capacity(2).build();
+        for body in ["a", "b", "c"] {
+            buffer.handle(record(LogLevel::DEBUG, body, "s1"));
+        }
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::ERROR, "boom", "s1"))),
+            ["b", "c", "boom"]
+        );
+    }
+
+    #[test]
+    fn oldest_key_is_evicted_at_max_keys() {
+        let buffer = TailBuffer::builder().max_keys(2).build();
+        buffer.handle(record(LogLevel::DEBUG, "a", "s1"));
+        buffer.handle(record(LogLevel::DEBUG, "b", "s2"));
+        buffer.handle(record(LogLevel::DEBUG, "c", "s3"));
+
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::ERROR, "x", "s1"))),
+            ["x"]
+        );
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::ERROR, "y", "s2"))),
+            ["b", "y"]
+        );
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::ERROR, "z", "s3"))),
+            ["c", "z"]
+        );
+    }
+
+    #[test]
+    fn discard_drops_the_buffer_of_a_closed_scope() {
+        let buffer = TailBuffer::builder().build();
+        buffer.handle(record(LogLevel::DEBUG, "a", "s1"));
+        buffer.discard("s1");
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::ERROR, "boom", "s1"))),
+            ["boom"]
+        );
+    }
+
+    #[test]
+    fn threshold_above_trigger_is_clamped_to_the_trigger() {
+        let buffer = TailBuffer::builder()
+            .threshold(LogLevel::ERROR)
+            .trigger(LogLevel::WARNING)
+            .build();
+        assert!(buffer.handle(record(LogLevel::INFO, "a", "s1")).is_empty());
+        assert_eq!(
+            bodies(&buffer.handle(record(LogLevel::WARNING, "warn", "s1"))),
+            ["a", "warn"]
+        );
+    }
+}
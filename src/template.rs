@@ -0,0 +1,108 @@
+use crate::types::Attribute;
+use crate::util::fnv1a64;
+
+pub(crate) fn render(template: &str, attrs: &[Attribute]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        rendered.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            rendered.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if let Some(after) = tail.strip_prefix('}') {
+            rendered.push('}');
+            rest = after;
+            continue;
+        }
+
+        match tail.find('}') {
+            Some(end) => {
+                let name = &tail[1..end];
+                match attrs.iter().rev().find(|attr| attr.key == name) {
+                    Some(attr) => rendered.push_str(&attr.value),
+                    None => rendered.push_str(&tail[..=end]),
+                }
+                rest = &tail[end + 1..];
+            }
+            None => {
+                rendered.push_str(tail);
+                rest = "";
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+pub(crate) fn template_hash(template: &str) -> String {
+    format!("{:016x}", fnv1a64(template.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs(pairs: &[(&str, &str)]) -> Vec<Attribute> {
+        pairs
+            .iter()
+            .map(|(key, value)| Attribute::new(*key, *value))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_placeholders() {
+        assert_eq!(
+            render(
+                "user {id} logged in from {ip}",
+                &attrs(&[("id", "42"), ("ip", "10.0.0.1")])
+            ),
+            "user 42 logged in from 10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn doubled_braces_are_escapes() {
+        assert_eq!(
+            render("{{id}} is {id}, }} and {{", &attrs(&[("id", "42")])),
+            "{id} is 42, } and {"
+        );
+    }
+
+    #[test]
+    fn missing_placeholders_are_left_verbatim() {
+        assert_eq!(
+            render("user {id} at {ip}", &attrs(&[("id", "42")])),
+            "user 42 at {ip}"
+        );
+    }
+
+    #[test]
+    fn unterminated_and_stray_braces_are_kept() {
+        assert_eq!(render("open {id", &attrs(&[("id", "42")])), "open {id");
+        assert_eq!(render("close } here", &[]), "close } here");
+    }
+
+    #[test]
+    fn last_repeated_key_wins() {
+        assert_eq!(render("{id}", &attrs(&[("id", "1"), ("id", "2")])), "2");
+    }
+
+    #[test]
+    fn template_hash_is_stable() {
+        assert_eq!(template_hash(""), "cbf29ce484222325");
+        assert_eq!(template_hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(
+            template_hash("user {id} logged in"),
+            template_hash("user {id} logged in")
+        );
+        assert_ne!(
+            template_hash("user {id} logged in"),
+            template_hash("user {id} logged out")
+        );
+    }
+}
@@ -1,11 +1,52 @@
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum LogLevel {
     INFO,
     WARNING,
     ERROR,
     DEBUG,
+    FATAL,
+}
+
+impl LogLevel {
+    pub(crate) const fn severity(self) -> u8 {
+        match self {
+            LogLevel::DEBUG => 0,
+            LogLevel::INFO => 1,
+            LogLevel::WARNING => 2,
+            LogLevel::ERROR => 3,
+            LogLevel::FATAL => 4,
+        }
+    }
+}
+
+impl From<LogLevel> for tracing::level_filters::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::DEBUG => tracing::level_filters::LevelFilter::DEBUG,
+            LogLevel::INFO => tracing::level_filters::LevelFilter::INFO,
+            LogLevel::WARNING => tracing::level_filters::LevelFilter::WARN,
+            LogLevel::ERROR | LogLevel::FATAL => tracing::level_filters::LevelFilter::ERROR,
+        }
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "debug" | "trace" => Ok(LogLevel::DEBUG),
+            "info" => Ok(LogLevel::INFO),
+            "warn" | "warning" => Ok(LogLevel::WARNING),
+            "error" => Ok(LogLevel::ERROR),
+            "fatal" | "critical" => Ok(LogLevel::FATAL),
+            _ => Err(format!("unknown log level: {}", s)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -23,7 +64,7 @@ impl Attribute {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct LogMessage {
     pub timestamp: String,
     pub body: String,
@@ -31,10 +72,60 @@ pub struct LogMessage {
     pub attributes: std::collections::HashMap<String, String>,
 }
 
+pub type LogRecord = LogMessage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetricMessage {
+    pub timestamp: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub metric_type: MetricType,
+    pub value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    pub attributes: std::collections::HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckInStatus {
+    InProgress,
+    Ok,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckInMessage {
+    pub timestamp: String,
+    pub monitor: String,
+    pub check_in_id: String,
+    pub status: CheckInStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub attributes: std::collections::HashMap<String, String>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageType {
     Logs,
+    Metrics,
+    #[serde(rename = "check_ins")]
+    CheckIns,
 }
 
 #[derive(Debug, Serialize)]
@@ -44,4 +135,8 @@ pub struct MessageBatch {
     pub msg_type: MessageType,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub logs: Vec<LogMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub metrics: Vec<MetricMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub check_ins: Vec<CheckInMessage>,
 }
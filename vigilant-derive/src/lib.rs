@@ -0,0 +1,300 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Field, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+#[proc_macro_derive(VigilantAttributes, attributes(vigilant))]
+pub fn derive_vigilant_attributes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<String>,
+    prefix: Option<String>,
+    skip: bool,
+    redact: bool,
+    flatten: bool,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let container = parse_options(&input.attrs)?;
+    if container.rename.is_some() || container.skip || container.redact || container.flatten {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "only `prefix` is supported on the struct itself",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "VigilantAttributes requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "VigilantAttributes can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut writes = Vec::new();
+    for field in fields {
+        if let Some(write) = expand_field(field)? {
+            writes.push(write);
+        }
+    }
+
+    let container_prefix = match &container.prefix {
+        Some(prefix) => quote! { ::vigilant::__private::join_key(prefix, #prefix) },
+        None => quote! { prefix.to_string() },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::vigilant::ToAttributes for #name #ty_generics #where_clause {
+            fn write_attributes(
+                &self,
+                prefix: &str,
+                attributes: &mut ::std::vec::Vec<::vigilant::Attribute>,
+            ) {
+                let prefix = #container_prefix;
+                let prefix = prefix.as_str();
+                #(#writes)*
+            }
+        }
+
+        impl #impl_generics ::std::iter::IntoIterator for #name #ty_generics #where_clause {
+            type Item = ::vigilant::Attribute;
+            type IntoIter = ::std::vec::IntoIter<::vigilant::Attribute>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                ::vigilant::ToAttributes::to_attributes(&self).into_iter()
+            }
+        }
+
+        impl #impl_generics ::std::iter::IntoIterator for &#name #ty_generics #where_clause {
+            type Item = ::vigilant::Attribute;
+            type IntoIter = ::std::vec::IntoIter<::vigilant::Attribute>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                ::vigilant::ToAttributes::to_attributes(self).into_iter()
+            }
+        }
+    })
+}
+
+fn expand_field(field: &Field) -> syn::Result<Option<TokenStream2>> {
+    let options = parse_options(&field.attrs)?;
+    if options.skip {
+        return Ok(None);
+    }
+
+    let ident = field.ident.as_ref().expect("named field");
+    let accessor = quote! { self.#ident };
+
+    if options.flatten {
+        if options.redact || options.rename.is_some() {
+            return Err(Error::new_spanned(
+                ident,
+                "`flatten` cannot be combined with `rename` or `redact`",
+            ));
+        }
+        let nested_prefix = match &options.prefix {
+            Some(nested) => quote! { &::vigilant::__private::join_key(prefix, #nested) },
+            None => quote! { prefix },
+        };
+        return Ok(Some(quote! {
+            ::vigilant::ToAttributes::write_attributes(&#accessor, #nested_prefix, attributes);
+        }));
+    }
+
+    if options.prefix.is_some() {
+        return Err(Error::new_spanned(
+            ident,
+            "`prefix` on a field requires `flatten`",
+        ));
+    }
+
+    let key = options
+        .rename
+        .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+    let push = |value: TokenStream2| {
+        quote! {
+            attributes.push(::vigilant::Attribute::new(
+                ::vigilant::__private::join_key(prefix, #key),
+                #value,
+            ));
+        }
+    };
+
+    if options.redact {
+        let write = push(quote! { ::vigilant::__private::REDACTED });
+        return Ok(Some(quote! {
+            let _ = &#accessor;
+            #write
+        }));
+    }
+
+    if option_inner(&field.ty).is_some() {
+        let write = push(quote! { ::std::string::ToString::to_string(value) });
+        return Ok(Some(quote! {
+            if let ::std::option::Option::Some(value) = &#accessor {
+                #write
+            }
+        }));
+    }
+
+    Ok(Some(push(
+        quote! { ::std::string::ToString::to_string(&#accessor) },
+    )))
+}
+
+fn parse_options(attrs: &[syn::Attribute]) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in attrs {
+        if !attr.path().is_ident("vigilant") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("prefix") {
+                options.prefix = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("redact") {
+                options.redact = true;
+            } else if meta.path.is_ident("flatten") {
+                options.flatten = true;
+            } else {
+                return Err(meta.error("unknown vigilant attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand;
+    use syn::{parse_quote, DeriveInput};
+
+    fn expand_err(input: DeriveInput) -> String {
+        expand(input)
+            .expect_err("expected the derive to be rejected")
+            .to_string()
+    }
+
+    #[test]
+    fn rejects_enums() {
+        let err = expand_err(parse_quote! {
+            enum Status { Active }
+        });
+        assert!(err.contains("only be derived for structs"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_tuple_structs() {
+        let err = expand_err(parse_quote! {
+            struct Id(u64);
+        });
+        assert!(err.contains("named fields"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_field_options_on_struct() {
+        let err = expand_err(parse_quote! {
+            #[vigilant(rename = "order")]
+            struct Order { id: u64 }
+        });
+        assert!(err.contains("only `prefix`"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_flatten_with_rename() {
+        let err = expand_err(parse_quote! {
+            struct Order {
+                #[vigilant(flatten, rename = "addr")]
+                address: Address,
+            }
+        });
+        assert!(err.contains("`flatten` cannot be combined"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_flatten_with_redact() {
+        let err = expand_err(parse_quote! {
+            struct Order {
+                #[vigilant(flatten, redact)]
+                address: Address,
+            }
+        });
+        assert!(err.contains("`flatten` cannot be combined"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_prefix_without_flatten() {
+        let err = expand_err(parse_quote! {
+            struct Order {
+                #[vigilant(prefix = "ship")]
+                address: String,
+            }
+        });
+        assert!(err.contains("requires `flatten`"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        let err = expand_err(parse_quote! {
+            struct Order {
+                #[vigilant(hide)]
+                id: u64,
+            }
+        });
+        assert!(err.contains("unknown vigilant attribute"), "{}", err);
+    }
+
+    #[test]
+    fn expands_to_attribute_impls() {
+        let tokens = expand(parse_quote! {
+            struct Order { id: u64 }
+        })
+        .unwrap()
+        .to_string();
+        assert!(tokens.contains(":: vigilant :: ToAttributes for Order"));
+        assert!(tokens.contains(":: std :: iter :: IntoIterator for Order"));
+        assert!(tokens.contains(":: std :: iter :: IntoIterator for & Order"));
+    }
+}
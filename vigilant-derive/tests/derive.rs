@@ -0,0 +1,106 @@
+use vigilant::{Attribute, ToAttributes, VigilantAttributes};
+
+#[derive(VigilantAttributes)]
+struct Address {
+    city: String,
+    #[vigilant(skip)]
+    #[allow(dead_code)]
+    street: String,
+}
+
+#[derive(VigilantAttributes)]
+#[vigilant(prefix = "order")]
+struct Order {
+    id: u64,
+    #[vigilant(rename = "customer.email")]
+    email: String,
+    #[vigilant(redact)]
+    card: String,
+    #[vigilant(flatten, prefix = "shipping")]
+    shipping: Address,
+    #[vigilant(flatten)]
+    billing: Option<Address>,
+    note: Option<String>,
+    coupon: Option<String>,
+    r#type: &'static str,
+}
+
+fn order() -> Order {
+    Order {
+        id: 42,
+        email: "jane@example.com".to_string(),
+        card: "4111 1111 1111 1111".to_string(),
+        shipping: Address {
+            city: "Oslo".to_string(),
+            street: "Karl Johans gate 1".to_string(),
+        },
+        billing: Some(Address {
+            city: "Bergen".to_string(),
+            street: "Bryggen 2".to_string(),
+        }),
+        note: Some("leave at door".to_string()),
+        coupon: None,
+        r#type: "web",
+    }
+}
+
+fn pairs(attributes: impl IntoIterator<Item = Attribute>) -> Vec<(String, String)> {
+    attributes
+        .into_iter()
+        .map(|attr| (attr.key, attr.value))
+        .collect()
+}
+
+fn pair(key: &str, value: &str) -> (String, String) {
+    (key.to_string(), value.to_string())
+}
+
+#[test]
+fn produces_prefixed_attributes_in_field_order() {
+    let order = order();
+    assert_eq!(
+        pairs(&order),
+        vec![
+            pair("order.id", "42"),
+            pair("order.customer.email", "jane@example.com"),
+            pair("order.card", "[REDACTED]"),
+            pair("order.shipping.city", "Oslo"),
+            pair("order.city", "Bergen"),
+            pair("order.note", "leave at door"),
+            pair("order.type", "web"),
+        ]
+    );
+    assert_eq!(pairs(order).len(), 7);
+}
+
+#[test]
+fn skipped_fields_are_left_out() {
+    let attributes = pairs(order());
+    assert!(attributes.iter().all(|(_, value)| !value.contains("gate")));
+}
+
+#[test]
+fn none_fields_are_left_out() {
+    let attributes = pairs(Order {
+        billing: None,
+        note: None,
+        ..order()
+    });
+    assert!(attributes.iter().all(|(key, _)| key != "order.note"));
+    assert!(attributes.iter().all(|(key, _)| key != "order.city"));
+}
+
+#[test]
+fn nested_prefix_is_joined_with_caller_prefix() {
+    let mut attributes = Vec::new();
+    order().shipping.write_attributes("ctx", &mut attributes);
+    assert_eq!(pairs(attributes), vec![pair("ctx.city", "Oslo")]);
+}
+
+#[test]
+fn can_be_passed_to_logger() {
+    let logger = vigilant::LoggerBuilder::new().noop(true).build();
+    let order = order();
+    logger.info_with_attrs("order placed", &order);
+    logger.info_with_attrs("order placed", order);
+}